@@ -0,0 +1,263 @@
+use std::{
+  io::Read,
+  path::{Path, PathBuf},
+  process::ExitCode,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use serde_json::Value;
+use shape::{
+  diff, json_schema_definitions, json_schema_document, Definitions, LockError, SchemaDocument, SchemaLock, ShapeOptions,
+  ShapeOptionsKind, Type, TypescriptExporter, Validate,
+};
+
+/// Exit code when the check of the command failed: invalid values, breaking changes or an outdated lock file
+const CHECK_FAILED: u8 = 1;
+/// Exit code for invalid arguments, unreadable files or invalid documents
+const ERROR: u8 = 2;
+
+/// Converts, validates and compares shape documents
+///
+/// A document is the JSON dumped by `shape::SchemaDocument::to_json` from a program that links the rust types,
+/// use `-` to read it from stdin
+#[derive(Debug, Parser)]
+#[command(name = "shape", version)]
+struct Cli {
+  #[command(subcommand)]
+  command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+  /// Renders every type of a document with a backend
+  Convert {
+    document: PathBuf,
+    #[arg(long, value_enum, default_value = "typescript")]
+    to: Backend,
+    #[arg(long, value_enum, default_value = "serialize")]
+    mode: Mode,
+    /// the root type of the JSON Schema, without it the schema only has `$defs`
+    #[arg(long = "type")]
+    ty: Option<String>,
+    /// write to a file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+  },
+  /// Validates JSON files, or NDJSON files with one value per line, against a type of a document
+  Validate {
+    document: PathBuf,
+    #[arg(long = "type")]
+    ty: String,
+    #[arg(long, value_enum, default_value = "deserialize")]
+    mode: Mode,
+    /// read every file as NDJSON, files ending in .ndjson or .jsonl always are
+    #[arg(long)]
+    ndjson: bool,
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+  },
+  /// Compares two versions of a document, fails if there is a breaking change
+  Diff { old: PathBuf, new: PathBuf },
+  /// Checks that a lock file matches a document
+  Lock {
+    document: PathBuf,
+    lock: PathBuf,
+    /// write the lock file instead of checking it
+    #[arg(long)]
+    update: bool,
+  },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Backend {
+  Typescript,
+  Zod,
+  JsonSchema,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Mode {
+  Serialize,
+  Deserialize,
+}
+
+impl Mode {
+  fn kind(self) -> ShapeOptionsKind {
+    match self {
+      Mode::Serialize => ShapeOptionsKind::Serialize,
+      Mode::Deserialize => ShapeOptionsKind::Deserialize,
+    }
+  }
+
+  fn options(self) -> ShapeOptions {
+    match self {
+      Mode::Serialize => ShapeOptions::for_serialize(),
+      Mode::Deserialize => ShapeOptions::for_deserialize(),
+    }
+  }
+}
+
+fn main() -> ExitCode {
+  let cli = Cli::parse();
+  match run(cli.command) {
+    Ok(code) => code,
+    Err(e) => {
+      eprintln!("error: {}", e);
+      ExitCode::from(ERROR)
+    }
+  }
+}
+
+fn run(command: Command) -> Result<ExitCode, String> {
+  match command {
+    Command::Convert { document, to, mode, ty, output } => {
+      let document = read_document(&document)?;
+      let definitions = document.definitions(mode.kind()).clone();
+      let out = match to {
+        Backend::Typescript => TypescriptExporter::with_definitions(mode.options(), definitions).export(),
+        Backend::Zod => TypescriptExporter::with_definitions(mode.options(), definitions).export_zod(),
+        Backend::JsonSchema => {
+          let schema = match ty {
+            Some(name) => json_schema_document(&root(&definitions, &name)?, &definitions),
+            None => json_schema_definitions(&definitions),
+          };
+          format!("{:#}\n", schema)
+        }
+      };
+
+      match output {
+        Some(path) => std::fs::write(&path, out).map_err(|e| format!("writing {}: {}", path.display(), e))?,
+        None => print!("{}", out),
+      }
+      Ok(ExitCode::SUCCESS)
+    }
+
+    Command::Validate { document, ty, mode, ndjson, files } => {
+      let document = read_document(&document)?;
+      let definitions = document.definitions(mode.kind());
+      let ty = root(definitions, &ty)?;
+
+      let mut invalid = 0;
+      for file in files {
+        let source = read(&file)?;
+        let lines = ndjson || matches!(file.extension().and_then(|e| e.to_str()), Some("ndjson" | "jsonl"));
+        let values: Vec<(String, &str)> = if lines {
+          source
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| (format!("{}:{}", file.display(), i + 1), line))
+            .collect()
+        } else {
+          vec![(file.display().to_string(), source.as_str())]
+        };
+
+        for (location, source) in values {
+          let value = match serde_json::from_str::<Value>(source) {
+            Ok(value) => value,
+            Err(e) => {
+              invalid += 1;
+              println!("{}: invalid JSON: {}", location, e);
+              continue;
+            }
+          };
+
+          if let Err(errors) = ty.validate_with(&value, definitions) {
+            invalid += 1;
+            for error in errors {
+              println!("{}: {}", location, error);
+            }
+          }
+        }
+      }
+
+      if invalid == 0 {
+        Ok(ExitCode::SUCCESS)
+      } else {
+        eprintln!("{} invalid value(s)", invalid);
+        Ok(ExitCode::from(CHECK_FAILED))
+      }
+    }
+
+    Command::Diff { old, new } => {
+      let old = read_document(&old)?;
+      let new = read_document(&new)?;
+
+      let mut breaking = false;
+      for mode in [Mode::Serialize, Mode::Deserialize] {
+        let old = old.definitions(mode.kind());
+        let new = new.definitions(mode.kind());
+        let label = format!("{:?}", mode).to_lowercase();
+
+        for (name, old_ty) in old.iter() {
+          match new.get(name) {
+            // clients may still name the type in either direction
+            None => {
+              breaking = true;
+              println!("{} {}: [breaking] type removed", label, name);
+            }
+            Some(new_ty) => {
+              let diff = diff(old_ty, new_ty, mode.kind());
+              breaking |= diff.is_breaking();
+              for change in diff.changes {
+                println!("{} {}: {}", label, name, change);
+              }
+            }
+          }
+        }
+
+        for (name, _) in new.iter() {
+          if !old.contains(name) {
+            println!("{} {}: type added", label, name);
+          }
+        }
+      }
+
+      if breaking {
+        Ok(ExitCode::from(CHECK_FAILED))
+      } else {
+        Ok(ExitCode::SUCCESS)
+      }
+    }
+
+    Command::Lock { document, lock, update } => {
+      let lock_file = SchemaLock::from_document(&read_document(&document)?);
+      if update {
+        lock_file.write(&lock).map_err(|e| format!("writing {}: {}", lock.display(), e))?;
+        return Ok(ExitCode::SUCCESS);
+      }
+
+      match lock_file.check(&lock) {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        Err(LockError::Io(e)) => Err(format!("reading {}: {}", lock.display(), e)),
+        Err(e) => {
+          eprintln!("{}", e);
+          Ok(ExitCode::from(CHECK_FAILED))
+        }
+      }
+    }
+  }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+  if path == Path::new("-") {
+    let mut source = String::new();
+    std::io::stdin().read_to_string(&mut source).map_err(|e| format!("reading stdin: {}", e))?;
+    Ok(source)
+  } else {
+    std::fs::read_to_string(path).map_err(|e| format!("reading {}: {}", path.display(), e))
+  }
+}
+
+fn read_document(path: &Path) -> Result<SchemaDocument, String> {
+  let value = serde_json::from_str::<Value>(&read(path)?).map_err(|e| format!("parsing {}: {}", path.display(), e))?;
+  SchemaDocument::from_json(&value).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn root(definitions: &Definitions, name: &str) -> Result<Type, String> {
+  if definitions.contains(name) {
+    Ok(Type::Ref(name.to_string()))
+  } else {
+    Err(format!("type {} not found in the document", name))
+  }
+}
@@ -0,0 +1,193 @@
+use std::{
+  path::PathBuf,
+  process::{Command, Output},
+};
+
+use shape::{SchemaDocument, SchemaLock, Shape};
+
+mod v1 {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    pub id: u64,
+    pub name: String,
+  }
+}
+
+mod v2 {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    pub id: u64,
+    pub email: String,
+  }
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Event {
+  id: u64,
+  kind: Option<String>,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[shape(generic)]
+struct Page<T> {
+  items: Vec<T>,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Bytes {
+  page: Page<u8>,
+}
+
+/// Writes a file in a directory of this test
+fn file(test: &str, name: &str, contents: &str) -> PathBuf {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(test);
+  std::fs::create_dir_all(&dir).unwrap();
+  let path = dir.join(name);
+  std::fs::write(&path, contents).unwrap();
+  path
+}
+
+fn document<T: Shape>(test: &str, name: &str) -> PathBuf {
+  let mut document = SchemaDocument::new();
+  document.add::<T>();
+  file(test, name, &document.to_json().to_string())
+}
+
+fn shape(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_shape")).args(args).output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+  String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn convert() {
+  let document = document::<Event>("convert", "document.json");
+  let document = document.to_str().unwrap();
+
+  let output = shape(&["convert", document]);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(stdout(&output), "export type Event = { id: number; kind: (string | null); };\n");
+
+  let output = shape(&["convert", document, "--to", "json-schema", "--mode", "deserialize", "--type", "Event"]);
+  assert_eq!(output.status.code(), Some(0));
+  let schema = serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap();
+  assert_eq!(schema["$ref"], "#/$defs/Event");
+  assert_eq!(schema["$defs"]["Event"]["required"], serde_json::json!(["id"]));
+
+  let output = shape(&["convert", document, "--to", "zod"]);
+  assert_eq!(output.status.code(), Some(0));
+  assert!(stdout(&output).contains("export const Event: z.ZodType<Event> = z.object("));
+}
+
+#[test]
+fn convert_generics_to_json_schema() {
+  let document = document::<Bytes>("convert_generics", "document.json");
+  let document = document.to_str().unwrap();
+
+  // JSON Schema has no generics, the instance gets its own definition
+  let output = shape(&["convert", document, "--to", "json-schema", "--type", "Bytes"]);
+  assert_eq!(output.status.code(), Some(0));
+  let schema = serde_json::from_str::<serde_json::Value>(&stdout(&output)).unwrap();
+  assert_eq!(schema["$defs"]["Bytes"]["properties"]["page"]["$ref"], "#/$defs/Page_number");
+  assert_eq!(schema["$defs"]["Page_number"]["properties"]["items"]["items"], serde_json::json!({ "type": "number" }));
+  assert_eq!(schema["$defs"].get("Page"), None);
+
+  // and agrees with validate
+  let invalid = file("convert_generics", "invalid.json", r#"{ "page": { "items": ["x"] } }"#);
+  let output = shape(&["validate", document, "--type", "Bytes", "--mode", "serialize", invalid.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn validate() {
+  let document = document::<Event>("validate", "document.json");
+  let valid = file("validate", "valid.json", r#"{ "id": 1, "kind": null }"#);
+  let events = file("validate", "events.ndjson", "{ \"id\": 1 }\n\n{ \"id\": \"2\" }\nnot json\n");
+
+  let output = shape(&["validate", document.to_str().unwrap(), "--type", "Event", valid.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(0));
+
+  let output = shape(&["validate", document.to_str().unwrap(), "--type", "Event", events.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(1));
+  let out = stdout(&output);
+  let path = events.display().to_string();
+  assert!(out.contains(&format!("{}:3: /id: expected number, found string \"2\"", path)), "{}", out);
+  assert!(out.contains(&format!("{}:4: invalid JSON", path)), "{}", out);
+  assert!(!out.contains(&format!("{}:1:", path)), "{}", out);
+
+  let output = shape(&["validate", document.to_str().unwrap(), "--type", "Missing", valid.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn diff() {
+  let old = document::<v1::User>("diff", "old.json");
+  let new = document::<v2::User>("diff", "new.json");
+
+  let output = shape(&["diff", old.to_str().unwrap(), old.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(0));
+  assert_eq!(stdout(&output), "");
+
+  let output = shape(&["diff", old.to_str().unwrap(), new.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(1));
+  let out = stdout(&output);
+  assert!(out.contains("serialize User: [breaking] /name: required property removed"), "{}", out);
+  assert!(out.contains("deserialize User: [breaking] /email: required property added"), "{}", out);
+}
+
+#[test]
+fn diff_removed_type() {
+  let mut old = SchemaDocument::new();
+  old.add::<v1::User>().add::<Event>();
+  let old = file("diff_removed_type", "old.json", &old.to_json().to_string());
+  let new = document::<v1::User>("diff_removed_type", "new.json");
+
+  let output = shape(&["diff", old.to_str().unwrap(), new.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(1));
+  let out = stdout(&output);
+  assert!(out.contains("serialize Event: [breaking] type removed"), "{}", out);
+
+  // adding a type is not breaking
+  let output = shape(&["diff", new.to_str().unwrap(), old.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(0));
+  assert!(stdout(&output).contains("serialize Event: type added"));
+}
+
+#[test]
+fn lock() {
+  let document = document::<Event>("lock", "document.json");
+  let lock = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("lock").join("shape.lock.ts");
+  let _ = std::fs::remove_file(&lock);
+
+  let output = shape(&["lock", document.to_str().unwrap(), lock.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(1));
+
+  let output = shape(&["lock", document.to_str().unwrap(), lock.to_str().unwrap(), "--update"]);
+  assert_eq!(output.status.code(), Some(0));
+
+  let mut expected = SchemaLock::new();
+  expected.add::<Event>();
+  assert_eq!(std::fs::read_to_string(&lock).unwrap(), expected.render());
+
+  let output = shape(&["lock", document.to_str().unwrap(), lock.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn invalid_document() {
+  let document = file("invalid_document", "document.json", r#"{ "version": 1 }"#);
+  let output = shape(&["convert", document.to_str().unwrap()]);
+  assert_eq!(output.status.code(), Some(2));
+  assert!(String::from_utf8(output.stderr).unwrap().contains("missing \"serialize\""));
+}
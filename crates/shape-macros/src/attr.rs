@@ -1,9 +1,11 @@
 use darling::{FromAttributes, FromMeta};
-use syn::Type;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use syn::{Attribute, LitStr, Type};
 
 #[derive(Debug, Default,FromAttributes)]
 #[darling(attributes(serde, shape))]
 pub struct ContainerAttrs {
+  pub rename: Option<Complex<String>>,
   pub rename_all: Option<Complex<Inflection>>,
   pub rename_all_fields: Option<Complex<Inflection>>,
   pub tag: Option<String>,
@@ -15,10 +17,19 @@ pub struct ContainerAttrs {
   pub into: Option<Type>,
   pub try_into: Option<Type>,
   pub default: Option<UnitOr<String>>,
+  pub deny_unknown_fields: Option<()>,
+  /// adds the type to the registry of exported types, Eg: #[shape(export)]
+  pub export: Option<()>,
+  /// generic types are exported once as a template with their type parameters, Eg: #[shape(generic)]
+  pub generic: Option<()>,
+  /// raw typescript type used as the shape, Eg: #[shape(type = "Date")]
+  #[darling(skip)]
+  pub custom: Option<String>,
+  /// a Shape type used in place of this one, Eg: #[shape(as = i64)]
+  #[darling(skip)]
+  pub shape_as: Option<Type>,
   
   // unused
-  // pub rename: Option<Complex<String>>,
-  // pub deny_unknown_fields: Option<()>,
   // pub remote: Option<Type>,
   // #[darling(rename = "crate")]
   // pub serde_crate: Option<String>, 
@@ -36,11 +47,12 @@ pub struct VariantAttrs {
   pub skip_deserializing: Option<()>,
   pub untagged: Option<()>,
 
+  #[darling(multiple)]
+  pub alias: Vec<String>,
+
   // altough unused, this fields must be present to avoid giving an erorr un valid serde attrs
   // unused
   #[allow(unused)]
-  pub alias: Option<String>,
-  #[allow(unused)]
   pub with: Option<String>,
   #[allow(unused)]
   pub serialize_with: Option<String>,
@@ -50,7 +62,6 @@ pub struct VariantAttrs {
   pub bound: Option<Complex<String>>,
   #[allow(unused)]
   pub borrow: Option<UnitOr<String>>,
-  #[allow(unused)]
   pub other: Option<()>,
 }
 
@@ -64,12 +75,23 @@ pub struct FieldAttrs {
   pub skip_serializing: Option<()>,
   pub skip_deserializing: Option<()>,
   pub skip_serializing_if: Option<String>,
+  /// raw typescript type used as the shape of the field, Eg: #[shape(type = "Date")]
+  #[darling(skip)]
+  pub custom: Option<String>,
+  /// a Shape type used in place of the field type, Eg: #[shape(as = i64)]
+  #[darling(skip)]
+  pub shape_as: Option<Type>,
+
+  #[darling(multiple)]
+  pub alias: Vec<String>,
+  /// the field can be absent, overrides the Option detection, Eg: #[shape(optional)]
+  pub optional: Option<()>,
+  /// the field must be present, overrides the Option detection, Eg: #[shape(required)]
+  pub required: Option<()>,
 
   // unused
   // altough unused, this fields must be present to avoid giving an erorr un valid serde attrs
   #[allow(unused)]
-  pub alias: Option<String>,
-  #[allow(unused)]
   pub with: Option<String>,
   #[allow(unused)]
   pub serialize_with: Option<String>,
@@ -84,6 +106,112 @@ pub struct FieldAttrs {
 }
 
 
+/// `type` and `as` are keywords so darling can't parse them,
+/// they are taken out of the #[shape(...)] attributes before the rest is given to darling
+#[derive(Debug, Default)]
+pub struct Overrides {
+  pub custom: Option<String>,
+  pub shape_as: Option<Type>,
+}
+
+pub fn take_overrides(attrs: &[Attribute]) -> Result<(Vec<Attribute>, Overrides), darling::Error> {
+  let mut overrides = Overrides::default();
+  let mut rest = vec![];
+
+  for attr in attrs {
+    let list = match &attr.meta {
+      syn::Meta::List(list) if attr.path().is_ident("shape") => list,
+      _ => {
+        rest.push(attr.clone());
+        continue;
+      }
+    };
+
+    // split the list in top level comma separated items,
+    // commas between angle brackets belong to the item, Eg: as = HashMap<String, i64>
+    let mut items: Vec<Vec<TokenTree>> = vec![vec![]];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for tt in list.tokens.clone() {
+      let item = items.last_mut().unwrap();
+      match &tt {
+        TokenTree::Punct(punct) if punct.as_char() == ',' && depth == 0 => {
+          items.push(vec![]);
+          continue;
+        }
+        TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+        // the > of -> is not a closing bracket, Eg: as = fn() -> u8
+        TokenTree::Punct(punct) if punct.as_char() == '>' && !arrow => depth = depth.saturating_sub(1),
+        _ => {}
+      }
+      arrow = matches!(&tt, TokenTree::Punct(punct) if punct.as_char() == '-' && punct.spacing() == Spacing::Joint);
+      item.push(tt);
+    }
+
+    let mut kept = vec![];
+    for item in items.into_iter().filter(|item| !item.is_empty()) {
+      let key = match (item.first(), item.get(1)) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(eq))) if eq.as_char() == '=' => ident.to_string(),
+        _ => String::new(),
+      };
+
+      let value = item.iter().skip(2).cloned().collect::<TokenStream>();
+      match key.as_str() {
+        "type" => {
+          let lit = syn::parse2::<LitStr>(value).map_err(|e| darling::Error::custom(e).with_span(&attr))?;
+          overrides.custom = Some(lit.value());
+        }
+        "as" => {
+          // as = i64 or as = "Vec<u8>"
+          let ty = match syn::parse2::<LitStr>(value.clone()) {
+            Ok(lit) => lit.parse::<Type>(),
+            Err(_) => syn::parse2::<Type>(value),
+          };
+          overrides.shape_as = Some(ty.map_err(|e| darling::Error::custom(e).with_span(&attr))?);
+        }
+        _ => kept.push(item.into_iter().collect::<TokenStream>()),
+      }
+    }
+
+    if !kept.is_empty() {
+      let mut attr = attr.clone();
+      if let syn::Meta::List(list) = &mut attr.meta {
+        list.tokens = quote::quote!{ #(#kept),* };
+      }
+      rest.push(attr);
+    }
+  }
+
+  if overrides.custom.is_some() && overrides.shape_as.is_some() {
+    return Err(darling::Error::custom("shape(type) and shape(as) can't be used together"))
+  }
+
+  Ok((rest, overrides))
+}
+
+impl ContainerAttrs {
+  pub fn parse(attrs: &[Attribute]) -> Result<Self, darling::Error> {
+    let (attrs, overrides) = take_overrides(attrs)?;
+    let mut container_attrs = Self::from_attributes(&attrs)?;
+    container_attrs.custom = overrides.custom;
+    container_attrs.shape_as = overrides.shape_as;
+    Ok(container_attrs)
+  }
+}
+
+impl FieldAttrs {
+  pub fn parse(attrs: &[Attribute]) -> Result<Self, darling::Error> {
+    let (attrs, overrides) = take_overrides(attrs)?;
+    let mut field_attrs = Self::from_attributes(&attrs)?;
+    if field_attrs.optional.is_some() && field_attrs.required.is_some() {
+      return Err(darling::Error::custom("shape(optional) and shape(required) can't be used together"))
+    }
+    field_attrs.custom = overrides.custom;
+    field_attrs.shape_as = overrides.shape_as;
+    Ok(field_attrs)
+  }
+}
+
 #[derive(Debug, FromMeta)]
 pub enum Inflection {
   #[darling(rename = "lowercase")]
@@ -361,4 +489,28 @@ impl<T: FromMeta> FromMeta for UnitOr<T> {
       None => <()>::from_none().map(|_| Self::Unit)
     }
   }
-}
\ No newline at end of file
+}
+/// The text of the `///` doc comments in `attrs`, `None` if there are none
+///
+/// Every `///` line is a `#[doc = " line"]` attribute, the space after the slashes is removed
+pub fn doc(attrs: &[Attribute]) -> Option<String> {
+  let mut lines = vec![];
+  for attr in attrs {
+    if let syn::Meta::NameValue(meta) = &attr.meta {
+      if meta.path.is_ident("doc") {
+        if let syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(lit), .. }) = &meta.value {
+          for line in lit.value().split('\n') {
+            lines.push(line.strip_prefix(' ').unwrap_or(line).trim_end().to_string());
+          }
+        }
+      }
+    }
+  }
+
+  let doc = lines.join("\n").trim_matches('\n').to_string();
+  if doc.trim().is_empty() {
+    None
+  } else {
+    Some(doc)
+  }
+}
@@ -1,9 +1,9 @@
 mod attr;
 
-use attr::{Complex, ContainerAttrs, FieldAttrs, VariantAttrs};
-use darling::{ast::GenericParamExt, FromAttributes}; 
-use syn::{spanned::Spanned, DeriveInput, GenericArgument, LitStr, Variant};
-use quote::quote;
+use attr::{doc, Complex, ContainerAttrs, FieldAttrs, VariantAttrs};
+use darling::FromAttributes;
+use syn::{spanned::Spanned, DeriveInput, LitStr, Variant};
+use quote::{format_ident, quote};
 
 #[proc_macro_derive(Shape, attributes(serde, shape))]
 pub fn shape(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -18,7 +18,9 @@ fn shape_inner(input: DeriveInput) -> Result<proc_macro2::TokenStream, darling::
 
   let ident = &input.ident;
   
-  let container_attrs = ContainerAttrs::from_attributes(&input.attrs)?;
+  let container_attrs = ContainerAttrs::parse(&input.attrs)?;
+
+  let deny_unknown_fields = container_attrs.deny_unknown_fields.is_some();
   
   let mut early = quote!{};
 
@@ -26,7 +28,7 @@ fn shape_inner(input: DeriveInput) -> Result<proc_macro2::TokenStream, darling::
     early = quote! {
       #early
       if options.is_serialize() {
-        return <#ty as ::shape::Shape>::shape(options);
+        return <#ty as ::shape::Shape>::shape_ref(options, definitions);
       }
     }
   }
@@ -35,238 +37,364 @@ fn shape_inner(input: DeriveInput) -> Result<proc_macro2::TokenStream, darling::
     early = quote! {
       #early
       if options.is_deserialize() {
-        return <#ty as ::shape::Shape>::shape(options);
+        return <#ty as ::shape::Shape>::shape_ref(options, definitions);
       }
     }
   }
 
 
-  let ty;
-  match input.data {
-    syn::Data::Struct(data) => {
-      match data.fields {
-        syn::Fields::Unit  => {
-          ty = quote!{ ::shape::Type::Null };  
-        },
-        syn::Fields::Unnamed(fields) => {
-          ty = fields_unnamed(&container_attrs, None, &fields)?;
-        },
-        syn::Fields::Named(fields) => {
-          ty = fields_named(&container_attrs, None, &fields)?;
-        }
-      }
-    },
-    syn::Data::Enum(data) => {
-      let mut variants = vec![];
-      for variant in data.variants {
+  // a missing value deserializes as None if the type this one is deserialized from does
+  let is_option = if let Some(from) = container_attrs.from.as_ref().or(container_attrs.try_from.as_ref()) {
+    quote!{ <#from as ::shape::Shape>::IS_OPTION }
+  } else if container_attrs.custom.is_some() {
+    quote!{ false }
+  } else if let Some(shape_as) = &container_attrs.shape_as {
+    quote!{ <#shape_as as ::shape::Shape>::IS_OPTION }
+  } else {
+    let fields = match &input.data {
+      syn::Data::Struct(data) if container_attrs.transparent.is_some() => data.fields.iter().collect::<Vec<_>>(),
+      _ => vec![],
+    };
+    match fields.as_slice() {
+      [field] => field_is_option(&field.ty, &FieldAttrs::parse(&field.attrs)?),
+      _ => quote!{ false },
+    }
+  };
 
-        let variant_attrs = VariantAttrs::from_attributes(&variant.attrs)?;
+  // the optionality of every field, a template is only used when it doesn't depend on the type arguments
+  let fields = match &input.data {
+    syn::Data::Struct(data) => data.fields.iter().collect::<Vec<_>>(),
+    syn::Data::Enum(data) => data.variants.iter().flat_map(|variant| variant.fields.iter()).collect(),
+    syn::Data::Union(_) => vec![],
+  };
+  let field_options = fields
+    .into_iter()
+    .map(|field| Ok(field_is_option(&field.ty, &FieldAttrs::parse(&field.attrs)?)))
+    .collect::<Result<Vec<_>, darling::Error>>()?;
 
-        if variant_attrs.skip.is_some() {
-          continue;
+  let ty;
+  if let Some(custom) = &container_attrs.custom {
+    ty = quote!{ ::shape::Type::Custom(String::from(#custom)) };
+  } else if let Some(shape_as) = &container_attrs.shape_as {
+    ty = quote!{ <#shape_as as ::shape::Shape>::shape_ref(options, definitions) };
+  } else {
+    match input.data {
+      syn::Data::Struct(data) => {
+        match data.fields {
+          syn::Fields::Unit  => {
+            ty = quote!{ ::shape::Type::Null };  
+          },
+          syn::Fields::Unnamed(fields) => {
+            ty = fields_unnamed(&container_attrs, None, &fields)?;
+          },
+          syn::Fields::Named(fields) => {
+            ty = fields_named(&container_attrs, None, ident, &fields)?;
+          }
         }
-        
-        let ident = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+      },
+      syn::Data::Enum(data) => {
+        let mut variants = vec![];
+        for variant in data.variants {
 
-        let mut get_name = quote! {
-          #[allow(unused)]
-          let mut name = #ident;
-        };
+          let variant_attrs = VariantAttrs::from_attributes(&variant.attrs)?;
 
-        match &container_attrs.rename_all {
-          None => {},
-          Some(complex) => match complex {
-            Complex::Single(rename_all) => {
-              let renamed = LitStr::new(&rename_all.apply(&variant.ident.to_string()), variant.ident.span());
-              get_name = quote! {
-                #get_name;
-                name = #renamed;
-              }
-            },
-            Complex::Complex { serialize, deserialize } => {
-              if let Some(serialize) = serialize {
-                let renamed = LitStr::new(&serialize.apply(&variant.ident.to_string()), variant.ident.span());
+          if variant_attrs.skip.is_some() {
+            continue;
+          }
+          
+          let ident = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+
+          let mut get_name = quote! {
+            #[allow(unused)]
+            let mut name = #ident;
+          };
+
+          match &container_attrs.rename_all {
+            None => {},
+            Some(complex) => match complex {
+              Complex::Single(rename_all) => {
+                let renamed = LitStr::new(&rename_all.apply(&variant.ident.to_string()), variant.ident.span());
                 get_name = quote! {
                   #get_name;
-                  if options.is_serialize() {
-                    name = #renamed;
+                  name = #renamed;
+                }
+              },
+              Complex::Complex { serialize, deserialize } => {
+                if let Some(serialize) = serialize {
+                  let renamed = LitStr::new(&serialize.apply(&variant.ident.to_string()), variant.ident.span());
+                  get_name = quote! {
+                    #get_name;
+                    if options.is_serialize() {
+                      name = #renamed;
+                    }
                   }
                 }
-              }
 
-              if let Some(deserialize) = deserialize {
-                let renamed = LitStr::new(&deserialize.apply(&variant.ident.to_string()), variant.ident.span());
-                get_name = quote! {
-                  #get_name;
-                  if options.is_deserialize() {
-                    name = #renamed;
-                  }
-                }    
+                if let Some(deserialize) = deserialize {
+                  let renamed = LitStr::new(&deserialize.apply(&variant.ident.to_string()), variant.ident.span());
+                  get_name = quote! {
+                    #get_name;
+                    if options.is_deserialize() {
+                      name = #renamed;
+                    }
+                  }    
+                }
               }
             }
-          }
-        };
+          };
 
-        match &variant_attrs.rename {
-          None => {},
-          Some(complex) => match complex {
-            Complex::Single(rename) => {
-              let name = LitStr::new(rename, variant.ident.span());
-              get_name = quote! {
-                let name = #name;
-              }
-            },
-            Complex::Complex { serialize, deserialize } => {
-              if let Some(serialize) = serialize {
-                let renamed = LitStr::new(serialize, variant.ident.span());
+          match &variant_attrs.rename {
+            None => {},
+            Some(complex) => match complex {
+              Complex::Single(rename) => {
+                let name = LitStr::new(rename, variant.ident.span());
                 get_name = quote! {
-                  #get_name;
-                  if options.is_serialize() {
-                    name = #renamed;
+                  let name = #name;
+                }
+              },
+              Complex::Complex { serialize, deserialize } => {
+                if let Some(serialize) = serialize {
+                  let renamed = LitStr::new(serialize, variant.ident.span());
+                  get_name = quote! {
+                    #get_name;
+                    if options.is_serialize() {
+                      name = #renamed;
+                    }
                   }
                 }
-              }
 
-              if let Some(deserialize) = deserialize {
-                let renamed = LitStr::new(deserialize, variant.ident.span());
-                get_name = quote! {
-                  #get_name;
-                  if options.is_deserialize() {
-                    name = #renamed;
-                  }
-                }    
+                if let Some(deserialize) = deserialize {
+                  let renamed = LitStr::new(deserialize, variant.ident.span());
+                  get_name = quote! {
+                    #get_name;
+                    if options.is_deserialize() {
+                      name = #renamed;
+                    }
+                  }    
+                }
               }
             }
           }
-        }
 
-        get_name = quote! {
-          {
-            #get_name;
-            name
-          }
-        };
+          get_name = quote! {
+            {
+              #get_name;
+              name
+            }
+          };
+
+          // the variant type for a given name, aliases share everything but the name
+          let variant_ty = |get_name: proc_macro2::TokenStream| -> Result<proc_macro2::TokenStream, darling::Error> {
+            let ty = match &variant.fields {
+              syn::Fields::Unit => {
+                if variant_attrs.untagged.is_some() || container_attrs.untagged.is_some() {
+                  quote! { ::shape::Type::Null }
+                } else {
+                  let literal = quote! {
+                    ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name)))
+                  };
+
+                  // serde deserializes any unknown tag into the #[serde(other)] variant.
+                  // A type can't exclude the known tags from `string`, so the catch all also accepts a known tag
+                  // without the fields of its variant, Eg: `{ "type": "Click" }`, that serde rejects
+                  let tag_ty = if variant_attrs.other.is_some() {
+                    quote! {
+                      if options.is_deserialize() {
+                        ::shape::Type::String
+                      } else {
+                        #literal
+                      }
+                    }
+                  } else {
+                    literal
+                  };
+
+                  match &container_attrs.tag {
+                    Some(tag) => {
+                      let tag = LitStr::new(tag, variant.span());
+                      let variant_description = description(&variant.attrs);
+                      quote!{ ::shape::Type::Object(
+                        ::shape::Object {
+                          properties: ::shape::indexmap::IndexMap::from([
+                            (
+                              String::from(#tag),
+                              ::shape::Property {
+                                optional: false,
+                                readonly: false,
+                                ty: #tag_ty,
+                                description: #variant_description,
+                              }
+                            )
+                          ]),
+                          exact: #deny_unknown_fields,
+                        })
+                      }
+                    }
 
-        let variant_ty = match &variant.fields {
-          syn::Fields::Unit => {
-            if variant_attrs.untagged.is_some() || container_attrs.untagged.is_some() {
-              quote! { ::shape::Type::Null }
-            } else {
-              match &container_attrs.tag {
-                Some(tag) => {
-                  let tag = LitStr::new(tag, variant.span());
-                  quote!{ ::shape::Type::Object(
-                    ::shape::Object {
-                      properties: ::shape::indexmap::IndexMap::from([
-                        (
-                          String::from(#tag),
-                          ::shape::Property {
-                            optional: false,
-                            readonly: false,
-                            ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name)))
-                          }
-                        )
-                      ])
-                    })
+                    None => tag_ty,
                   }
                 }
+              }
 
-                None => {
-                  quote! {
-                    ::shape::Type::Literal(
-                      ::shape::Literal::String(
-                        String::from(#get_name)
-                      )
-                    )
-                  }
-                }
+              syn::Fields::Unnamed(fields) => {
+                let fields = fields_unnamed(&container_attrs, Some(&variant_attrs), fields)?;
+                join_enum_fields(fields, get_name, &variant, &variant_attrs, &container_attrs)
               }
-            }
-          }
 
-          syn::Fields::Unnamed(fields) => {
-            let fields = fields_unnamed(&container_attrs, Some(&variant_attrs), fields)?;
-            join_enum_fields(fields, get_name, &variant, &variant_attrs, &container_attrs)
-          }
+              syn::Fields::Named(fields) => {
+                let fields = fields_named(&container_attrs, Some(&variant_attrs), &variant.ident, fields)?;
+                join_enum_fields(fields, get_name, &variant, &variant_attrs, &container_attrs)
+              }
+            };
+            Ok(ty)
+          };
 
-          syn::Fields::Named(fields) => {
-            let fields = fields_named(&container_attrs, Some(&variant_attrs), fields)?;
-            join_enum_fields(fields, get_name, &variant, &variant_attrs, &container_attrs)
-          }
-        };
+          let aliases = if variant_attrs.untagged.is_some() || container_attrs.untagged.is_some() {
+            vec![]
+          } else {
+            variant_attrs.alias
+              .iter()
+              .map(|alias| {
+                let alias = LitStr::new(alias, variant.ident.span());
+                variant_ty(quote!{ #alias })
+              })
+              .collect::<Result<Vec<_>, _>>()?
+          };
 
-        let skip_serializing = variant_attrs.skip_serializing.is_some();
-        let skip_deserializing = variant_attrs.skip_deserializing.is_some();
-      
-        variants.push(quote! {
-          if options.is_serialize() && !#skip_serializing {
-            variants.push(#variant_ty);
-          }
+          let variant_ty = variant_ty(get_name)?;
 
-          if options.is_deserialize() && !#skip_deserializing {
-            variants.push(#variant_ty);  
-          }
-        });
-      };
+          let skip_serializing = variant_attrs.skip_serializing.is_some();
+          let skip_deserializing = variant_attrs.skip_deserializing.is_some();
+        
+          variants.push(quote! {
+            if options.is_serialize() && !#skip_serializing {
+              variants.push(#variant_ty);
+            }
 
-      ty = quote! {
-        let mut variants = vec![];
-        #(#variants;)*
-        if variants.is_empty() {
-          ::shape::Type::Never
-        } else {
-          ::shape::Type::Or(variants)
+            if options.is_deserialize() && !#skip_deserializing {
+              variants.push(#variant_ty);
+              #( variants.push(#aliases); )*
+            }
+          });
+        };
+
+        ty = quote! {
+          let mut variants = vec![];
+          #(#variants;)*
+          if variants.is_empty() {
+            ::shape::Type::Never
+          } else {
+            ::shape::Type::Or(variants)
+          }
         }
+      },
+      syn::Data::Union(_) => {
+        return Err(darling::Error::custom("Unions are not yet supported"))
       }
-    },
-    syn::Data::Union(_) => {
-      return Err(darling::Error::custom("Unions are not yet supported"))
     }
   }
 
   let generics = &input.generics;
   
   let (impl_generics, type_generics, where_clause ) = generics.split_for_impl();
-  let shape_where =  {
-    generics.type_params()
-      .filter_map(|param| {
-        let ty = param.as_type_param()?;
-        Some(
-          quote! {
-            #ty: ::shape::Shape
-          }
-        )
-      })
-      .collect::<Vec<proc_macro2::TokenStream>>()
+  // every type parameter must be a Shape, added to the predicates of the type
+  let mut where_clause = where_clause.cloned().unwrap_or_else(|| syn::WhereClause {
+    where_token: Default::default(),
+    predicates: Default::default(),
+  });
+  for param in generics.type_params() {
+    let ident = &param.ident;
+    where_clause.predicates.push(syn::parse_quote!{ #ident: ::shape::Shape });
+  }
+  let where_clause = if where_clause.predicates.is_empty() { quote!{} } else { quote!{ #where_clause } };
+
+  // the doc of the type is kept on its definition
+  let describe = match doc(&input.attrs) {
+    None => quote!{},
+    Some(doc) => quote! {
+      if let ::shape::Type::Ref(name) | ::shape::Type::Generic(::shape::Generic { name, .. }) = &ty {
+        definitions.describe(name, #doc);
+      }
+    },
   };
 
-  let where_clause = match where_clause {
-    None => {
-      if shape_where.is_empty() {
-        quote!{}  
-      } else {
-        quote! {
-          where #(#shape_where),*
+  // with #[shape(generic)] generic types are also described as a template, with the type parameters replaced by placeholders.
+  // The placeholders must satisfy the bounds of the field types, so this is opt-in
+  let type_params = generics.type_params().map(|param| param.ident.clone()).collect::<Vec<syn::Ident>>();
+  let template = if container_attrs.generic.is_none() {
+    quote!{}
+  } else if type_params.is_empty() {
+    return Err(darling::Error::custom("#[shape(generic)] needs a type parameter").with_span(&input.ident));
+  } else {
+    let markers = type_params.iter().map(|param| format_ident!("__ShapeParam{}", param)).collect::<Vec<syn::Ident>>();
+    let names = type_params.iter().map(|param| LitStr::new(&param.to_string(), param.span())).collect::<Vec<LitStr>>();
+    let body = replace_idents(quote!{ #early #ty }, &type_params, &markers);
+    // Eg: a field `v: T` is optional for `T = Option<u8>` but not in the template, those instances get their own definition
+    let (field_options, template_options): (Vec<_>, Vec<_>) = field_options
+      .iter()
+      .map(|option| (option.clone(), replace_idents(option.clone(), &type_params, &markers)))
+      .filter(|(option, template)| option.to_string() != template.to_string())
+      .unzip();
+    quote! {
+      #(
+        struct #markers;
+        impl ::shape::Shape for #markers {
+          fn shape(_: &::shape::ShapeOptions) -> ::shape::Type {
+            ::shape::Type::Param(String::from(#names))
+          }
         }
+      )*
+
+      if definitions.generics() #(&& #field_options == #template_options)* {
+        let args = vec![#(<#type_params as ::shape::Shape>::shape_ref(options, definitions)),*];
+        let ty = definitions.define_generic(concat!(module_path!(), "::", stringify!(#ident)), &[#(#names),*], args, |definitions| {
+          #body
+        });
+        #describe
+        return ty;
       }
-    },
-    Some(original) => {
-      let mut clause = quote! { #original };
-      if !shape_where.is_empty() {
-        clause = quote! {
-          #clause, #(#shape_where),*
+    }
+  };
+
+  let register = if container_attrs.export.is_none() {
+    quote!{}
+  } else if !type_params.is_empty() || generics.const_params().next().is_some() {
+    return Err(darling::Error::custom("#[shape(export)] is not supported on generic types, export an instance with add_as instead").with_span(&input.generics));
+  } else {
+    // borrowed fields are registered with their 'static version
+    let lifetimes = generics.lifetimes().map(|_| quote!{ 'static }).collect::<Vec<_>>();
+    let registered = if lifetimes.is_empty() { quote!{ #ident } } else { quote!{ #ident<#(#lifetimes),*> } };
+    quote! {
+      ::shape::inventory::submit! {
+        ::shape::Registered {
+          name: stringify!(#ident),
+          module_path: module_path!(),
+          shape_ref: <#registered as ::shape::Shape>::shape_ref,
         }
       }
-      clause
     }
   };
 
   let implementation = quote! {
-    
+    #register
+
     impl #impl_generics ::shape::Shape for #ident #type_generics #where_clause {
+      const IS_OPTION: bool = #is_option;
+
       fn shape(options: &::shape::ShapeOptions) -> ::shape::Type {
-        #early
-        #ty
+        let mut definitions = ::shape::Definitions::new();
+        let ty = <Self as ::shape::Shape>::shape_ref(options, &mut definitions);
+        definitions.resolve(&ty)
+      }
+
+      fn shape_ref(options: &::shape::ShapeOptions, definitions: &mut ::shape::Definitions) -> ::shape::Type {
+        #template
+        let ty = definitions.define(::std::any::type_name::<Self>(), |definitions| {
+          #early
+          #ty
+        });
+        #describe
+        ty
       }
     }
   };
@@ -280,11 +408,9 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
     let inner = fields.unnamed.first().unwrap();
     let ty = &inner.ty;
 
-    let field_attrs = FieldAttrs::from_attributes(&inner.attrs)?;
+    let field_attrs = FieldAttrs::parse(&inner.attrs)?;
 
-    let inner = quote!{
-      <#ty as ::shape::Shape>::shape(options)
-    };
+    let inner = field_shape(ty, &field_attrs);
 
     let field_skip_serializing = field_attrs.skip_serializing.is_some();
     let field_skip_serializing_if = field_attrs.skip_serializing_if.is_some();
@@ -297,8 +423,9 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
       if field_attrs.skip.is_some() {
         quote!{
           // empty tuple
-          ::shape::Type::Tuple(Tuple {
+          ::shape::Type::Tuple(::shape::Tuple {
             items: vec![],
+            optional: 0,
             rest: None,
           })
         }
@@ -329,12 +456,12 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
 
   } else {
 
-    let mut variants = vec![];
+    let mut items = vec![];
 
     let mut prev_has_default = None;
 
     for field in &fields.unnamed { 
-      let field_attrs = FieldAttrs::from_attributes(&field.attrs)?;
+      let field_attrs = FieldAttrs::parse(&field.attrs)?;
 
       if field_attrs.skip.is_some() {
         continue;
@@ -358,34 +485,18 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
         prev_has_default = Some(true);
       }
 
-      let ty = &field.ty;
+      let ty = field_shape(&field.ty, &field_attrs);
       
-      variants.push(quote!{
-        let ty = <#ty as ::shape::Shape>::shape(options);
+      // each item is the field shape and whether it can be missing
+      items.push(quote!{
+        let ty = #ty;
         if options.is_serialize() {
-          if #skip_serializing {
-            // do nothing
-          } else if #skip_serializing_if {
-            let prev = variants.clone();
-            for mut variant in prev {
-              variant.push(ty.clone());
-              variants.push(variant);
-            }
-          } else {
-            for item in variants.iter_mut() {
-              item.push(ty.clone());
-            }
+          if !#skip_serializing {
+            items.push((ty, #skip_serializing_if));
           }
         } else {
-          if #skip_deserializing {
-            // do nothing
-          } else if #has_default || #container_has_default {
-            let mut last = variants.iter().last().cloned().unwrap();
-            last.push(ty.clone());
-            variants.push(last);
-          } else {
-            let nth = variants.len() - 1;
-            variants.get_mut(nth).unwrap().push(ty.clone());
+          if !#skip_deserializing {
+            items.push((ty, #has_default || #container_has_default));
           }
         };
       });
@@ -393,22 +504,18 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
 
     let shape = quote! {
       {
-        let mut variants: Vec<Vec<::shape::Type>> = vec![vec![]];
-        #( { #variants }; )*
-        if variants.len() == 1 {
+        let mut items: Vec<(::shape::Type, bool)> = vec![];
+        #( { #items }; )*
+        if options.is_serialize() {
+          ::shape::skip_if::tuple(items)
+        } else {
+          // fields with default are the last ones
+          let optional = items.iter().rev().take_while(|(_, optional)| *optional).count();
           ::shape::Type::Tuple(::shape::Tuple {
-            items: variants.pop().unwrap(),
+            items: items.into_iter().map(|(ty, _)| ty).collect(),
+            optional,
             rest: None,
           })
-        } else {
-          ::shape::Type::Or(
-            variants.into_iter().map(|items| {
-              ::shape::Type::Tuple(::shape::Tuple {
-                items,
-                rest: None,
-              })
-            }).collect::<Vec<::shape::Type>>()
-          )
         }
       }
     };
@@ -417,24 +524,56 @@ fn fields_unnamed(container_attrs: &ContainerAttrs, _variant_attrs: Option<&Vari
   }
 }
 
-fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&VariantAttrs>, fields: &syn::FieldsNamed) -> Result<proc_macro2::TokenStream, darling::Error> {
+fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&VariantAttrs>, ident: &syn::Ident, fields: &syn::FieldsNamed) -> Result<proc_macro2::TokenStream, darling::Error> {
+
+  let exact = container_attrs.deny_unknown_fields.is_some();
+
+  // serde adds the name of tagged structs as a tag property when serializing
+  let struct_tag = match (variant_attrs, &container_attrs.tag) {
+    (None, Some(tag)) => {
+      let name = match &container_attrs.rename {
+        Some(Complex::Single(rename)) => rename.clone(),
+        Some(Complex::Complex { serialize: Some(rename), .. }) => rename.clone(),
+        _ => ident.to_string(),
+      };
+      let tag = LitStr::new(tag, ident.span());
+      let name = LitStr::new(&name, ident.span());
+      quote! {
+        if options.is_serialize() {
+          properties.insert(
+            String::from(#tag),
+            ::shape::Property {
+              optional: false,
+              readonly: false,
+              ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#name))),
+              description: None,
+            }
+          );
+        }
+      }
+    }
+    _ => quote!{},
+  };
   
   let shape = if fields.named.is_empty() {
     quote!{
-      ::shape::Type::Object(::shape::Object {
-        properties: ::shape::indexmap::IndexMap::new(),
-      })
+      {
+        #[allow(unused_mut)]
+        let mut properties = ::shape::indexmap::IndexMap::<String, ::shape::Property>::new();
+        #struct_tag
+        ::shape::Type::Object(::shape::Object {
+          properties,
+          exact: #exact,
+        })
+      }
     }
   } else {
     
     if container_attrs.transparent.is_some() {
       if fields.named.len() == 1 {
         let field = fields.named.first().unwrap();
-        let ty = &field.ty;
-        let decl = quote!{
-          <#ty as ::shape::Shape>::shape(options)
-        };
-        return Ok(decl);
+        let field_attrs = FieldAttrs::parse(&field.attrs)?;
+        return Ok(field_shape(&field.ty, &field_attrs));
       } else {
         return Err(
           darling::Error::custom("transparent structs can only have one field")
@@ -445,14 +584,17 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
 
     let declare_properties = quote! {
       let mut properties = ::shape::indexmap::IndexMap::<String, ::shape::Property>::new();
+      #struct_tag
     };
 
     let mut populate_properties = quote!{};
 
     let mut flattened = vec![];
 
+    let mut has_aliases = false;
+
     for field in &fields.named {
-      let field_attrs = FieldAttrs::from_attributes(&field.attrs)?;
+      let field_attrs = FieldAttrs::parse(&field.attrs)?;
 
       if field_attrs.skip.is_some() {
         continue;
@@ -463,7 +605,7 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
         continue;
       }
 
-      let is_option = is_option(&field.ty);
+      let is_option = field_is_option(&field.ty, &field_attrs);
 
       let field_skip_serializing = field_attrs.skip_serializing.is_some();
       let field_skip_serializing_if = field_attrs.skip_serializing_if.is_some();
@@ -472,23 +614,29 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
       let container_has_default = container_attrs.default.is_some();
       let field_skip_deserializing = field_attrs.skip_deserializing.is_some();
       
-      let optional = quote!{
-        if #is_option && options.option_is_optional {
-          true
-        } else if options.is_serialize() {
-          if #field_skip_serializing_if {
-            true
-          } else {
-            false
-          }
-        } else {
-          if #field_has_default || #container_has_default {
+      let optional = if field_attrs.optional.is_some() {
+        quote!{ true }
+      } else if field_attrs.required.is_some() {
+        quote!{ false }
+      } else {
+        quote!{
+          if #is_option && options.option_is_optional {
             true
+          } else if options.is_serialize() {
+            if #field_skip_serializing_if {
+              true
+            } else {
+              false
+            }
           } else {
-            false
+            if #field_has_default || #container_has_default {
+              true
+            } else {
+              false
+            }
           }
         }
-      }; 
+      };
 
       let readonly = false;
       
@@ -551,12 +699,12 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
           }
         }
 
-        match field_attrs.rename {
+        match &field_attrs.rename {
           None => {},
           Some(complex) => {
             match complex {
               Complex::Single(rename) => {
-                let name = LitStr::new(&rename, ident.span());
+                let name = LitStr::new(rename, ident.span());
                 // override previous get logic
                 get_name = quote!{
                   let name = #name;
@@ -564,7 +712,7 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
               },
               Complex::Complex { serialize, deserialize } => {
                 if let Some(serialize) = serialize {
-                  let name = LitStr::new(&serialize, ident.span());
+                  let name = LitStr::new(serialize, ident.span());
                   get_name = quote!{
                     #get_name;
                     if options.is_serialize() {
@@ -574,7 +722,7 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
                 }
 
                 if let Some(deserialize) = deserialize {
-                  let name = LitStr::new(&deserialize, ident.span());
+                  let name = LitStr::new(deserialize, ident.span());
                   get_name = quote!{
                     #get_name;
                     if options.is_deserialize() {
@@ -595,31 +743,73 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
         get_name
       };
 
-      let ty = &field.ty;
+      let ty = field_shape(&field.ty, &field_attrs);
+      let description = description(&field.attrs);
 
-      populate_properties = quote! {
-        #populate_properties
-        
-        if (options.is_serialize() && !#field_skip_serializing) || (options.is_deserialize() && !#field_skip_deserializing) {
-          properties.insert(
-            String::from({ #get_name }), 
-            ::shape::Property {
+      if field_attrs.alias.is_empty() {
+        populate_properties = quote! {
+          #populate_properties
+          
+          if (options.is_serialize() && !#field_skip_serializing) || (options.is_deserialize() && !#field_skip_deserializing) {
+            properties.insert(
+              String::from({ #get_name }), 
+              ::shape::Property {
+                readonly: #readonly,
+                optional: #optional,
+                ty: #ty,
+                description: #description,
+              }
+            );
+          }        
+        }
+      } else {
+        // serde accepts the field under any of its aliases when deserializing, see shape::derive::aliased
+        has_aliases = true;
+        let aliases = field_attrs.alias.iter().map(|alias| LitStr::new(alias, ident.span()));
+        populate_properties = quote! {
+          #populate_properties
+
+          if (options.is_serialize() && !#field_skip_serializing) || (options.is_deserialize() && !#field_skip_deserializing) {
+            let name = String::from({ #get_name });
+            let property = ::shape::Property {
               readonly: #readonly,
               optional: #optional,
-              ty: <#ty as ::shape::Shape>::shape(options),
+              ty: #ty,
+              description: #description,
+            };
+            if options.is_deserialize() {
+              let keys = [name, #(String::from(#aliases)),*];
+              aliased.push(keys.into_iter().map(|key| (key, property.clone())).collect());
+            } else {
+              properties.insert(name, property);
             }
-          );
-        }        
+          }
+        }
       }
     }
 
-    let not_flatten = quote! {
-      {
-        #declare_properties
-        #populate_properties
-        ::shape::Type::Object(::shape::Object {
-          properties,
-        })
+    // serde doesn't support deny_unknown_fields with flatten
+    let exact = exact && flattened.is_empty();
+
+    let not_flatten = if has_aliases {
+      quote! {
+        {
+          #declare_properties
+          let mut aliased = Vec::<Vec<(String, ::shape::Property)>>::new();
+          #populate_properties
+          ::shape::derive::aliased(properties, aliased, #exact)
+        }
+      }
+    } else {
+      quote! {
+        {
+          #declare_properties
+          #populate_properties
+          ::shape::Type::Object(::shape::Object {
+            properties,
+            exact: #exact,
+          })
+        }
       }
     };
 
@@ -639,11 +829,11 @@ fn fields_named(container_attrs: &ContainerAttrs, variant_attrs: Option<&Variant
         let container_has_default = container_attrs.default.is_some();
         let field_skip_deserializing = field_attrs.skip_deserializing.is_some();
 
-        let ty = &field.ty;
+        let ty = field_shape(&field.ty, &field_attrs);
 
         shape = quote! {
           #shape
-          let flat = <#ty as ::shape::Shape>::shape(options);
+          let flat = #ty;
           if options.is_serialize() {
             if #field_skip_serializing {
               // do nothing
@@ -687,6 +877,9 @@ fn join_enum_fields(
   variant_attrs: &VariantAttrs,
   container_attrs: &ContainerAttrs
 ) -> proc_macro2::TokenStream {
+  let deny_unknown_fields = container_attrs.deny_unknown_fields.is_some();
+  // the doc of a variant goes on its tag, or on its key for externally tagged enums
+  let variant_description = description(&variant.attrs);
   if variant_attrs.untagged.is_some() || container_attrs.untagged.is_some() {
     fields
   } else {
@@ -704,7 +897,8 @@ fn join_enum_fields(
                     ::shape::Property {
                       readonly: false,
                       optional: false,
-                      ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name)))
+                      ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name))),
+                      description: #variant_description,
                     }
                   ),
 
@@ -713,32 +907,28 @@ fn join_enum_fields(
                     ::shape::Property {
                       readonly: false,
                       optional: false,
-                      ty: #fields
+                      ty: #fields,
+                      description: None,
                     }
                   )
-                ])
+                ]),
+                exact: #deny_unknown_fields,
               }),
             }
           }
           
           None => {
             quote! {
-              ::shape::Type::And(vec![
-                ::shape::Type::Object(::shape::Object {
-                  properties: ::shape::indexmap::IndexMap::from([
-                    (
-                      String::from(#tag),
-                      ::shape::Property {
-                        readonly: false,
-                        optional: false,
-                        ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name)))
-                      }
-                    )
-                  ])
-                }),
-
+              ::shape::derive::tagged(
+                String::from(#tag),
+                ::shape::Property {
+                  readonly: false,
+                  optional: false,
+                  ty: ::shape::Type::Literal(::shape::Literal::String(String::from(#get_name))),
+                  description: #variant_description,
+                },
                 #fields,
-              ])
+              )
             }
           }
         }
@@ -754,9 +944,11 @@ fn join_enum_fields(
                   readonly: false,
                   optional: false,
                   ty: #fields,
+                  description: #variant_description,
                 }
               )
-            ])
+            ]),
+            exact: false,
           })
         }
       }
@@ -764,50 +956,95 @@ fn join_enum_fields(
   }
 }
 
-// TODO: there must be a better way to do this
-fn is_option(ty: &syn::Type) -> bool {
-  
-  let last = match ty {
-    syn::Type::Path(path) => {
-      match path.path.segments.last() {
-        Some(last) => last,
-        None => return false,
-      }
+/// `Some(String::from(doc))` for the doc comments of a field or variant, `None` without them
+fn description(attrs: &[syn::Attribute]) -> proc_macro2::TokenStream {
+  match doc(attrs) {
+    Some(doc) => quote!{ Some(String::from(#doc)) },
+    None => quote!{ None },
+  }
+}
+
+/// The shape of a field, honoring #[shape(type = "...")] and #[shape(as = ...)]
+fn field_shape(ty: &syn::Type, field_attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+  let shape = match (&field_attrs.custom, &field_attrs.shape_as) {
+    (Some(custom), _) => quote!{ ::shape::Type::Custom(String::from(#custom)) },
+    (None, Some(shape_as)) => {
+      quote!{ <#shape_as as ::shape::Shape>::shape_ref(options, definitions) }
     },
-    _ => return false,
+    (None, None) => quote!{ <#ty as ::shape::Shape>::shape_ref(options, definitions) },
   };
 
-  if last.ident != "Option" {
-    return false;
+  // a serialized field didn't match its skip_serializing_if predicate,
+  // for well known predicates that narrows the shape
+  let narrowed = match field_attrs.skip_serializing_if.as_deref().and_then(known_predicate) {
+    Some("is_none") => quote!{ ::shape::skip_if::none(ty) },
+    Some("is_empty") => quote!{ ::shape::skip_if::empty(ty) },
+    Some("is_false") => quote!{ ::shape::skip_if::false_(ty) },
+    _ => return shape,
+  };
+
+  quote!{
+    {
+      let ty = #shape;
+      if options.is_serialize() {
+        #narrowed
+      } else {
+        ty
+      }
+    }
   }
+}
 
-  let generics = match &last.arguments {
-    syn::PathArguments::AngleBracketed(generics) => generics,
-    _ => return false,
+/// The name of a well known skip_serializing_if predicate, given as a bare name or as the method of a std type,
+/// Eg: `is_false`, `Option::is_none` and `std::vec::Vec::is_empty`, but not `my_mod::is_empty`
+fn known_predicate(path: &str) -> Option<&'static str> {
+  const COLLECTIONS: &[&str] = &[
+    "Vec", "VecDeque", "String", "str", "HashMap", "BTreeMap", "HashSet", "BTreeSet", "IndexMap", "IndexSet",
+  ];
+
+  let segments = path.split("::").map(str::trim).collect::<Vec<&str>>();
+  let (name, owner, prefix) = match segments.as_slice() {
+    [name] => (*name, None, &[][..]),
+    [prefix @ .., owner, name] => (*name, Some(*owner), prefix),
+    [] => return None,
   };
-  
-  if generics.args.len() != 1 {
-    return false;
+  if !prefix.is_empty() && !matches!(prefix[0], "std" | "core" | "alloc" | "indexmap") {
+    return None;
   }
 
-  matches!(&generics.args[0], GenericArgument::Type(_))
+  match (owner, name) {
+    (None, "is_none") | (Some("Option"), "is_none") => Some("is_none"),
+    (None, "is_empty") => Some("is_empty"),
+    (Some(owner), "is_empty") if COLLECTIONS.contains(&owner) => Some("is_empty"),
+    (None, "is_false") => Some("is_false"),
+    _ => None,
+  }
 }
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-
-  #[test]
-  fn test_is_option() {
-    assert!(is_option(&syn::parse_str::<syn::Type>("::core::option::Option<i32>").unwrap()));
-    assert!(is_option(&syn::parse_str::<syn::Type>("::std::option::Option<i32>").unwrap()));
-    assert!(is_option(&syn::parse_str::<syn::Type>("Option<i32>").unwrap()));
-    assert!(is_option(&syn::parse_str::<syn::Type>("option::Option<i32>").unwrap()));
-    assert!(is_option(&syn::parse_str::<syn::Type>("Self::Option<i32>").unwrap()));
-    assert!(!is_option(&syn::parse_str::<syn::Type>("i32").unwrap()));
-    assert!(!is_option(&syn::parse_str::<syn::Type>("Option").unwrap()));
-    assert!(!is_option(&syn::parse_str::<syn::Type>("Option<'a, u64>").unwrap()));
-    assert!(!is_option(&syn::parse_str::<syn::Type>("::core::option::Option<'a, u64>").unwrap()));
-    assert!(!is_option(&syn::parse_str::<syn::Type>("::core::option::Option<'a>").unwrap()));
+/// Whether a missing field deserializes as None, this comes from [Shape::IS_OPTION] of the field type
+fn field_is_option(ty: &syn::Type, field_attrs: &FieldAttrs) -> proc_macro2::TokenStream {
+  match (&field_attrs.custom, &field_attrs.shape_as) {
+    (Some(_), _) => quote!{ false },
+    (None, Some(shape_as)) => quote!{ <#shape_as as ::shape::Shape>::IS_OPTION },
+    (None, None) => quote!{ <#ty as ::shape::Shape>::IS_OPTION },
   }
-}
\ No newline at end of file
+}
+
+/// Replaces every `from[i]` identifier in `tokens` with `to[i]`
+fn replace_idents(tokens: proc_macro2::TokenStream, from: &[syn::Ident], to: &[syn::Ident]) -> proc_macro2::TokenStream {
+  tokens
+    .into_iter()
+    .map(|tree| match tree {
+      proc_macro2::TokenTree::Ident(ident) => match from.iter().position(|from| *from == ident) {
+        Some(i) => proc_macro2::TokenTree::Ident(to[i].clone()),
+        None => proc_macro2::TokenTree::Ident(ident),
+      },
+      proc_macro2::TokenTree::Group(group) => {
+        let mut replaced = proc_macro2::Group::new(group.delimiter(), replace_idents(group.stream(), from, to));
+        replaced.set_span(group.span());
+        proc_macro2::TokenTree::Group(replaced)
+      }
+      tree => tree,
+    })
+    .collect()
+}
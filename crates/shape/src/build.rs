@@ -0,0 +1,221 @@
+//! Helpers to write bindings from a `build.rs` or a test
+//!
+//! Eg:
+//! ```ignore
+//! let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+//! exporter.add_registered();
+//! Bindings::new(exporter).layout(Layout::PerModule).write("frontend/src/bindings")?;
+//! ```
+
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  io,
+  path::{Path, PathBuf},
+};
+
+use crate::{
+  definitions::{collect_refs, identifier},
+  TypescriptExporter, Type,
+};
+
+/// How declarations are split into files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layout {
+  /// Every declaration in one file, Eg: `Layout::Single("bindings".into())` writes `bindings.ts`
+  Single(String),
+  /// One file per rust module, Eg: `my_crate::api::User` is written to `my_crate/api.ts`,
+  /// declarations without a rust type (added with `add_as` or loaded from a document) go to `index.ts`
+  PerModule,
+  /// One file per declaration, Eg: `User.ts`
+  PerType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+  /// `export type` declarations, see [TypescriptExporter::export]
+  Typescript,
+  /// types and zod schemas, see [TypescriptExporter::export_zod]
+  Zod,
+}
+
+/// Renders the declarations of a [TypescriptExporter] into a set of files that import each other
+#[derive(Debug, Clone)]
+pub struct Bindings {
+  exporter: TypescriptExporter,
+  layout: Layout,
+  backend: Backend,
+}
+
+/// The file used by [Layout::PerModule] for declarations without a module
+const INDEX: &str = "index";
+
+impl Bindings {
+  pub fn new(exporter: TypescriptExporter) -> Self {
+    Self { exporter, layout: Layout::PerModule, backend: Backend::Typescript }
+  }
+
+  pub fn layout(mut self, layout: Layout) -> Self {
+    self.layout = layout;
+    self
+  }
+
+  pub fn backend(mut self, backend: Backend) -> Self {
+    self.backend = backend;
+    self
+  }
+
+  /// Returns every file as (path relative to the output directory, contents), sorted by path
+  pub fn files(&self) -> Vec<(PathBuf, String)> {
+    let declarations = self.exporter.declarations();
+
+    // declaration => file without extension, Eg: `my_crate/api`
+    let files = declarations
+      .iter()
+      .map(|(name, _)| (name.clone(), self.file(name)))
+      .collect::<BTreeMap<String, String>>();
+
+    let mut grouped = BTreeMap::<&str, Vec<(&str, &Type)>>::new();
+    for (name, ty) in &declarations {
+      grouped.entry(&files[name]).or_default().push((name, ty));
+    }
+
+    grouped
+      .into_iter()
+      .map(|(file, declarations)| {
+        // file => names imported from it
+        let mut imports = BTreeMap::<&str, BTreeSet<String>>::new();
+        for (_, ty) in &declarations {
+          let mut refs = vec![];
+          collect_refs(ty, &mut refs);
+          for name in refs.into_iter().map(identifier) {
+            match files.get(&name) {
+              Some(from) if from != file => {
+                imports.entry(from).or_default().insert(name);
+              }
+              _ => {}
+            }
+          }
+        }
+
+        let mut out = String::new();
+        if self.backend == Backend::Zod {
+          out.push_str("import { z } from \"zod\";\n");
+        }
+        for (from, names) in &imports {
+          let names = names.iter().map(String::as_str).collect::<Vec<&str>>().join(", ");
+          match self.backend {
+            Backend::Typescript => out.push_str(&format!("import type {{ {} }} from \"{}\";\n", names, relative(file, from))),
+            Backend::Zod => out.push_str(&format!("import {{ {} }} from \"{}\";\n", names, relative(file, from))),
+          }
+        }
+
+        for (i, (name, ty)) in declarations.into_iter().enumerate() {
+          match self.backend {
+            Backend::Typescript => {
+              if i == 0 && !imports.is_empty() {
+                out.push('\n');
+              }
+              out.push_str(&self.exporter.declaration(name, ty));
+            }
+            Backend::Zod => {
+              out.push('\n');
+              out.push_str(&self.exporter.zod_declaration(name, ty));
+            }
+          }
+        }
+
+        (PathBuf::from(format!("{}.ts", file)), out)
+      })
+      .collect()
+  }
+
+  /// Writes every file into `dir`, files that already have the same contents are not touched.
+  /// Returns the paths of the files that were written
+  pub fn write(&self, dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let mut written = vec![];
+    for (path, contents) in self.files() {
+      let path = dir.as_ref().join(path);
+      if write_if_changed(&path, &contents)? {
+        written.push(path);
+      }
+    }
+    Ok(written)
+  }
+
+  /// The file of a declaration without extension
+  fn file(&self, name: &str) -> String {
+    match &self.layout {
+      Layout::Single(file) => file.clone(),
+      Layout::PerType => name.to_string(),
+      Layout::PerModule => {
+        let definitions = self.exporter.definitions();
+        definitions
+          .iter()
+          .find(|(definition, _)| identifier(definition) == name)
+          .and_then(|(definition, _)| definitions.rust_name(definition))
+          .and_then(module)
+          .map(|module| module.replace("::", "/"))
+          .unwrap_or_else(|| INDEX.to_string())
+      }
+    }
+  }
+}
+
+/// Writes `contents` to `path` unless it already has them, creating the parent directories.
+/// Returns whether the file was written
+pub fn write_if_changed(path: impl AsRef<Path>, contents: &str) -> io::Result<bool> {
+  let path = path.as_ref();
+  match std::fs::read(path) {
+    Ok(current) if current == contents.as_bytes() => return Ok(false),
+    Ok(_) => {}
+    Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+    Err(e) => return Err(e),
+  }
+
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  std::fs::write(path, contents)?;
+  Ok(true)
+}
+
+/// The module of a rust type name, Eg: `my_crate::api::Page<my_crate::User>` => `my_crate::api`
+fn module(rust_name: &str) -> Option<&str> {
+  let path = rust_name.split('<').next().unwrap_or(rust_name);
+  path.rsplit_once("::").map(|(module, _)| module)
+}
+
+/// The import path of the file `to` from the file `from`, Eg: `a/b` and `a/c/d` => `./c/d`
+fn relative(from: &str, to: &str) -> String {
+  let from = from.split('/').collect::<Vec<&str>>();
+  let to = to.split('/').collect::<Vec<&str>>();
+  // only directories are shared, the last segment is the file
+  let dir = &from[..from.len() - 1];
+  let common = dir.iter().zip(&to[..to.len() - 1]).take_while(|(a, b)| a == b).count();
+
+  let mut out = if common == dir.len() { String::from("./") } else { "../".repeat(dir.len() - common) };
+  out.push_str(&to[common..].join("/"));
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_relative() {
+    assert_eq!(relative("a/b", "a/c/d"), "./c/d");
+    assert_eq!(relative("a/c/d", "a/b"), "../b");
+    assert_eq!(relative("index", "my_crate/api"), "./my_crate/api");
+    assert_eq!(relative("my_crate/api", "index"), "../index");
+    assert_eq!(relative("a/b/c", "d"), "../../d");
+    assert_eq!(relative("a/b/c", "a/b"), "../b");
+  }
+
+  #[test]
+  fn test_module() {
+    assert_eq!(module("my_crate::api::User"), Some("my_crate::api"));
+    assert_eq!(module("my_crate::Page<my_crate::api::User>"), Some("my_crate"));
+    assert_eq!(module("User"), None);
+  }
+}
@@ -0,0 +1,494 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use crate::{Array, Generic, Object, Property, Record, Shape, ShapeOptions, ToTypescript, Tuple, Type};
+
+/// A table of named types, referenced from other types with [Type::Ref]
+///
+/// Derived [Shape] implementations register themselves here from [Shape::shape_ref],
+/// definitions are kept in the order they were first seen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Definitions {
+  // rust type name => definition name
+  names: HashMap<&'static str, String>,
+  types: IndexMap<String, Type>,
+  // definition name => type parameters, only for generic definitions
+  params: HashMap<String, Vec<String>>,
+  // definition name => doc comment of the type
+  descriptions: HashMap<String, String>,
+  // old name => new name of the definitions renamed by a name collision
+  renames: HashMap<String, String>,
+  generics: bool,
+}
+
+impl Definitions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Like [Definitions::new] but generic types with #[shape(generic)] are defined once as a template and referenced with [Type::Generic],
+  /// by default every instantiation of a generic type is a separate definition
+  pub fn with_generics() -> Self {
+    Self { generics: true, ..Self::default() }
+  }
+
+  /// Whether generic types are defined as templates, see [Definitions::define_generic]
+  pub fn generics(&self) -> bool {
+    self.generics
+  }
+
+  /// Registers the shape of `T` (and every named type it uses) and returns it,
+  /// for named types the returned value is a [Type::Ref]
+  pub fn add<T: Shape + ?Sized>(&mut self, options: &ShapeOptions) -> Type {
+    let ty = T::shape_ref(options, self);
+    self.renamed(&ty)
+  }
+
+  /// Returns a reference to the type identified by `id` (usually [std::any::type_name]),
+  /// `f` is only called the first time an `id` is seen, so recursive types end in a [Type::Ref] to themselves
+  pub fn define(&mut self, id: &'static str, f: impl FnOnce(&mut Self) -> Type) -> Type {
+    if let Some(name) = self.names.get(id) {
+      return Type::Ref(name.clone());
+    }
+
+    let short = qualified_name(id, 0);
+    let colliding = self.names.keys().copied().filter(|other| qualified_name(other, 0) == short).collect::<Vec<_>>();
+    let short = if colliding.is_empty() {
+      short
+    } else {
+      // rust types with the same name are all named with their modules, Eg: `a_User` and `b_User`,
+      // so their names don't depend on which one was seen first
+      let ids = colliding.iter().copied().chain([id]).collect::<Vec<_>>();
+      let max = ids.iter().map(|id| id.matches("::").count()).max().unwrap_or(0);
+      let depth = (1..=max)
+        .find(|depth| {
+          let names = ids.iter().map(|id| qualified_name(id, *depth)).collect::<HashSet<_>>();
+          names.len() == ids.len()
+        })
+        .unwrap_or(max);
+      for other in colliding {
+        self.rename(&self.names[other].clone(), &qualified_name(other, depth));
+      }
+      qualified_name(id, depth)
+    };
+
+    let mut name = short.clone();
+    let mut n = 1;
+    while self.types.contains_key(&name) {
+      n += 1;
+      name = format!("{short}{n}");
+    }
+
+    self.names.insert(id, name.clone());
+    // placeholder until the type is computed, this keeps the definitions in discovery order
+    self.types.insert(name.clone(), Type::Never);
+
+    let ty = f(self);
+    // the type or its references may have been renamed by a collision while it was computed
+    let ty = self.renamed(&ty);
+    let name = self.names[id].clone();
+    self.types.insert(name.clone(), ty);
+
+    Type::Ref(name)
+  }
+
+  /// Renames the definition `from` and every reference to it
+  fn rename(&mut self, from: &str, to: &str) {
+    if from == to {
+      return;
+    }
+
+    for name in self.names.values_mut().filter(|name| *name == from) {
+      *name = to.to_string();
+    }
+    if let Some(params) = self.params.remove(from) {
+      self.params.insert(to.to_string(), params);
+    }
+    if let Some(description) = self.descriptions.remove(from) {
+      self.descriptions.insert(to.to_string(), description);
+    }
+    for name in self.renames.values_mut().filter(|name| *name == from) {
+      *name = to.to_string();
+    }
+    self.renames.insert(from.to_string(), to.to_string());
+
+    let types = std::mem::take(&mut self.types);
+    self.types = types
+      .into_iter()
+      .map(|(name, ty)| (if name == from { to.to_string() } else { name }, self.renamed(&ty)))
+      .collect();
+  }
+
+  /// Returns `ty` with the references to renamed definitions updated,
+  /// needed for types that were returned before a name collision, Eg: a root added with [Definitions::add]
+  pub(crate) fn renamed(&self, ty: &Type) -> Type {
+    if self.renames.is_empty() {
+      return ty.clone();
+    }
+
+    map(ty, &mut |ty| match ty {
+      Type::Ref(name) => Some(Type::Ref(self.renames.get(name).unwrap_or(name).clone())),
+      Type::Generic(generic) => Some(Type::Generic(Generic {
+        name: self.renames.get(&generic.name).unwrap_or(&generic.name).clone(),
+        args: generic.args.iter().map(|arg| self.renamed(arg)).collect(),
+      })),
+      _ => None,
+    })
+  }
+
+  /// Returns a [Type::Generic] reference to the template identified by `id` with `args`,
+  /// `f` is only called the first time an `id` is seen and returns the template with a [Type::Param] for each of `params`
+  pub fn define_generic(&mut self, id: &'static str, params: &[&str], args: Vec<Type>, f: impl FnOnce(&mut Self) -> Type) -> Type {
+    let name = match self.names.get(id) {
+      Some(name) => name.clone(),
+      None => {
+        self.define(id, f);
+        let name = self.names[id].clone();
+        self.params.insert(name.clone(), params.iter().map(|param| param.to_string()).collect());
+        name
+      }
+    };
+
+    Type::Generic(Generic { name, args: args.iter().map(|arg| self.renamed(arg)).collect() })
+  }
+
+  /// Adds or replaces a definition by name, Eg: a named alias or a definition loaded from a [crate::SchemaDocument].
+  /// `params` are the type parameters of generic definitions, empty otherwise
+  pub fn insert(&mut self, name: impl Into<String>, params: Vec<String>, ty: Type) {
+    let name = name.into();
+    if params.is_empty() {
+      self.params.remove(&name);
+    } else {
+      self.params.insert(name.clone(), params);
+    }
+    self.types.insert(name, ty);
+  }
+
+  /// Sets the description of a definition, Eg: the `///` doc comment of a derived type
+  pub fn describe(&mut self, name: &str, description: impl Into<String>) {
+    self.descriptions.insert(name.to_string(), description.into());
+  }
+
+  pub fn description(&self, name: &str) -> Option<&str> {
+    self.descriptions.get(name).map(String::as_str)
+  }
+
+  /// The type parameters of a generic definition
+  pub fn params(&self, name: &str) -> Option<&[String]> {
+    self.params.get(name).map(Vec::as_slice)
+  }
+
+  /// Returns the generic definition `name` with its type parameters replaced by `args`
+  pub fn instantiate(&self, name: &str, args: &[Type]) -> Option<Type> {
+    let template = self.types.get(name)?;
+    match self.params.get(name) {
+      Some(params) => Some(substitute(template, params, args)),
+      None => Some(template.clone()),
+    }
+  }
+
+  /// The rust type a definition was registered with, Eg: `my_crate::api::User`,
+  /// `None` for definitions added with [Definitions::insert]
+  pub fn rust_name(&self, name: &str) -> Option<&'static str> {
+    self.names.iter().find(|(_, n)| *n == name).map(|(id, _)| *id)
+  }
+
+  /// Returns `ty` and these definitions without templates, for backends without generics, Eg: JSON Schema.
+  /// Every [Type::Generic] is replaced with a [Type::Ref] to a definition of the instance, Eg: `Page<number>`
+  pub fn monomorphize(&self, ty: &Type) -> (Type, Definitions) {
+    let mut out = Definitions::new();
+    for (name, definition) in self.iter() {
+      if self.params(name).is_none() {
+        let definition = self.instances(definition, &mut out);
+        out.insert(name, vec![], definition);
+        if let Some(description) = self.description(name) {
+          out.describe(name, description);
+        }
+      }
+    }
+    let ty = self.instances(ty, &mut out);
+    (ty, out)
+  }
+
+  fn instances(&self, ty: &Type, out: &mut Definitions) -> Type {
+    map(ty, &mut |ty| match ty {
+      Type::Generic(generic) => {
+        let args = generic.args.iter().map(|arg| self.instances(arg, out)).collect::<Vec<Type>>();
+        let name = format!("{}<{}>", generic.name, args.iter().map(Type::to_typescript).collect::<Vec<String>>().join(", "));
+        if !out.contains(&name) {
+          // placeholder until the instance is computed, recursive instances end in a reference to themselves
+          out.insert(name.clone(), vec![], Type::Never);
+          let instance = self.instantiate(&generic.name, &args).unwrap_or(Type::Never);
+          let instance = self.instances(&instance, out);
+          out.insert(name.clone(), vec![], instance);
+          if let Some(description) = self.description(&generic.name) {
+            out.describe(&name, description);
+          }
+        }
+        Some(Type::Ref(name))
+      }
+      _ => None,
+    })
+  }
+
+  /// Returns `ty` with the keys of its records resolved, JSON keys are strings so a backend renders a key from its structure,
+  /// Eg: the key of `HashMap<Id, String>` is the number of `Id` and not a reference to the schema of `Id`
+  pub(crate) fn resolve_keys(&self, ty: &Type) -> Type {
+    map(ty, &mut |ty| match ty {
+      Type::Record(record) => Some(Type::Record(Record {
+        key: Box::new(self.resolve(&record.key)),
+        value: Box::new(self.resolve_keys(&record.value)),
+        ..record.clone()
+      })),
+      _ => None,
+    })
+  }
+
+  pub fn get(&self, name: &str) -> Option<&Type> {
+    self.types.get(name)
+  }
+
+  pub fn contains(&self, name: &str) -> bool {
+    self.types.contains_key(name)
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = (&str, &Type)> {
+    self.types.iter().map(|(name, ty)| (name.as_str(), ty))
+  }
+
+  pub fn len(&self) -> usize {
+    self.types.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.types.is_empty()
+  }
+
+  /// Inlines every [Type::Ref] in `ty` with its definition,
+  /// references to a type that is already being inlined are kept to end the recursion
+  pub fn resolve(&self, ty: &Type) -> Type {
+    self.resolve_inner(ty, &mut vec![])
+  }
+
+  fn resolve_inner<'a>(&'a self, ty: &Type, stack: &mut Vec<&'a str>) -> Type {
+    match ty {
+      Type::Ref(name) => match self.types.get_key_value(name) {
+        None => ty.clone(),
+        Some((name, def)) => {
+          if stack.contains(&name.as_str()) {
+            return ty.clone();
+          }
+          stack.push(name);
+          let resolved = self.resolve_inner(def, stack);
+          stack.pop();
+          resolved
+        }
+      },
+      Type::Generic(generic) => {
+        let args = generic.args.iter().map(|arg| self.resolve_inner(arg, stack)).collect::<Vec<Type>>();
+        match self.types.get_key_value(&generic.name) {
+          Some((name, _)) if !stack.contains(&name.as_str()) => {
+            let instance = self.instantiate(name, &args).unwrap();
+            stack.push(name);
+            let resolved = self.resolve_inner(&instance, stack);
+            stack.pop();
+            resolved
+          }
+          _ => Type::Generic(Generic { name: generic.name.clone(), args }),
+        }
+      }
+      Type::Null
+      | Type::Undefined
+      | Type::Never
+      | Type::Boolean
+      | Type::Number
+      | Type::String
+      | Type::Literal(_)
+      | Type::Custom(_)
+      | Type::Param(_) => ty.clone(),
+      Type::Tuple(tuple) => Type::Tuple(Tuple {
+        items: tuple.items.iter().map(|item| self.resolve_inner(item, stack)).collect(),
+        rest: tuple.rest.as_ref().map(|rest| Box::new(self.resolve_inner(rest, stack))),
+        optional: tuple.optional,
+      }),
+      Type::Array(array) => Type::Array(Array {
+        item: Box::new(self.resolve_inner(&array.item, stack)),
+      }),
+      Type::Object(object) => Type::Object(Object {
+        properties: object
+          .properties
+          .iter()
+          .map(|(key, prop)| {
+            let prop = Property {
+              optional: prop.optional,
+              readonly: prop.readonly,
+              ty: self.resolve_inner(&prop.ty, stack),
+              description: prop.description.clone(),
+            };
+            (key.clone(), prop)
+          })
+          .collect(),
+        exact: object.exact,
+      }),
+      Type::Record(record) => Type::Record(Record {
+        optional: record.optional,
+        readonly: record.readonly,
+        key: Box::new(self.resolve_inner(&record.key, stack)),
+        value: Box::new(self.resolve_inner(&record.value, stack)),
+      }),
+      Type::And(types) => Type::And(types.iter().map(|t| self.resolve_inner(t, stack)).collect()),
+      Type::Or(types) => Type::Or(types.iter().map(|t| self.resolve_inner(t, stack)).collect()),
+    }
+  }
+}
+
+/// Replaces every [Type::Param] in `ty` with the argument at the same position
+fn substitute(ty: &Type, params: &[String], args: &[Type]) -> Type {
+  map(ty, &mut |ty| match ty {
+    Type::Param(name) => match params.iter().position(|param| param == name) {
+      Some(i) => Some(args.get(i).cloned().unwrap_or(Type::Never)),
+      None => Some(ty.clone()),
+    },
+    _ => None,
+  })
+}
+
+/// Rebuilds `ty` replacing every type for which `f` returns `Some`, the others are rebuilt from their replaced children
+fn map(ty: &Type, f: &mut dyn FnMut(&Type) -> Option<Type>) -> Type {
+  if let Some(ty) = f(ty) {
+    return ty;
+  }
+
+  let mut sub = |ty: &Type| map(ty, f);
+  match ty {
+    Type::Null
+    | Type::Undefined
+    | Type::Never
+    | Type::Boolean
+    | Type::Number
+    | Type::String
+    | Type::Literal(_)
+    | Type::Custom(_)
+    | Type::Ref(_)
+    | Type::Param(_) => ty.clone(),
+    Type::Generic(generic) => Type::Generic(Generic {
+      name: generic.name.clone(),
+      args: generic.args.iter().map(&mut sub).collect(),
+    }),
+    Type::Tuple(tuple) => Type::Tuple(Tuple {
+      items: tuple.items.iter().map(&mut sub).collect(),
+      rest: tuple.rest.as_ref().map(|rest| Box::new(sub(rest))),
+      optional: tuple.optional,
+    }),
+    Type::Array(array) => Type::Array(Array { item: Box::new(sub(&array.item)) }),
+    Type::Object(object) => Type::Object(Object {
+      properties: object
+        .properties
+        .iter()
+        .map(|(key, prop)| (key.clone(), Property { ty: sub(&prop.ty), ..prop.clone() }))
+        .collect(),
+      exact: object.exact,
+    }),
+    Type::Record(record) => Type::Record(Record {
+      key: Box::new(sub(&record.key)),
+      value: Box::new(sub(&record.value)),
+      ..record.clone()
+    }),
+    Type::And(types) => Type::And(types.iter().map(&mut sub).collect()),
+    Type::Or(types) => Type::Or(types.iter().map(&mut sub).collect()),
+  }
+}
+
+/// Strips the module paths from a rust type name but their last `depth` modules,
+/// Eg: `alloc::vec::Vec<crate::a::User>` => `Vec<User>` with depth 0 and `vec_Vec<a_User>` with depth 1
+fn qualified_name(id: &str, depth: usize) -> String {
+  let mut out = String::with_capacity(id.len());
+  let mut path = Vec::new();
+  let mut segment = String::new();
+  let mut chars = id.chars().peekable();
+  let flush = |out: &mut String, path: &mut Vec<String>, segment: &mut String| {
+    path.push(std::mem::take(segment));
+    out.push_str(&path[path.len().saturating_sub(depth + 1)..].join("_"));
+    path.clear();
+  };
+  while let Some(c) = chars.next() {
+    if c == ':' && chars.peek() == Some(&':') {
+      chars.next();
+      path.push(std::mem::take(&mut segment));
+    } else if c.is_alphanumeric() || c == '_' {
+      segment.push(c);
+    } else {
+      flush(&mut out, &mut path, &mut segment);
+      out.push(c);
+    }
+  }
+  flush(&mut out, &mut path, &mut segment);
+  out
+}
+
+/// Turns a definition name into a valid identifier, Eg: `Page<User>` => `Page_User`
+pub(crate) fn identifier(name: &str) -> String {
+  let mut out = String::with_capacity(name.len());
+  for c in name.chars() {
+    if c.is_alphanumeric() || c == '_' || c == '$' {
+      out.push(c);
+    } else if !out.is_empty() && !out.ends_with('_') {
+      out.push('_');
+    }
+  }
+  let out = out.trim_end_matches('_');
+  if out.starts_with(|c: char| c.is_ascii_digit()) {
+    format!("_{out}")
+  } else {
+    out.to_string()
+  }
+}
+
+/// Pushes the name of every [Type::Ref] found in `ty` to `refs`
+pub(crate) fn collect_refs<'a>(ty: &'a Type, refs: &mut Vec<&'a str>) {
+  match ty {
+    Type::Ref(name) => refs.push(name),
+    Type::Generic(generic) => {
+      refs.push(&generic.name);
+      generic.args.iter().for_each(|t| collect_refs(t, refs));
+    }
+    Type::Null
+    | Type::Undefined
+    | Type::Never
+    | Type::Boolean
+    | Type::Number
+    | Type::String
+    | Type::Literal(_)
+    | Type::Custom(_)
+    | Type::Param(_) => {}
+    Type::Tuple(tuple) => {
+      tuple.items.iter().for_each(|t| collect_refs(t, refs));
+      if let Some(rest) = &tuple.rest {
+        collect_refs(rest, refs);
+      }
+    }
+    Type::Array(array) => collect_refs(&array.item, refs),
+    Type::Object(object) => object.properties.values().for_each(|prop| collect_refs(&prop.ty, refs)),
+    Type::Record(record) => {
+      collect_refs(&record.key, refs);
+      collect_refs(&record.value, refs);
+    }
+    Type::And(types) | Type::Or(types) => types.iter().for_each(|t| collect_refs(t, refs)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_qualified_name() {
+    assert_eq!(qualified_name("Node", 0), "Node");
+    assert_eq!(qualified_name("crate::tree::Node", 0), "Node");
+    assert_eq!(qualified_name("crate::Page<alloc::string::String>", 0), "Page<String>");
+    assert_eq!(qualified_name("a::Map<a::b::K, alloc::vec::Vec<&str>>", 0), "Map<K, Vec<&str>>");
+    assert_eq!(qualified_name("crate::tree::Node", 1), "tree_Node");
+    assert_eq!(qualified_name("a::Map<a::b::K, alloc::vec::Vec<&str>>", 1), "a_Map<b_K, vec_Vec<&str>>");
+  }
+}
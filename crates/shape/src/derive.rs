@@ -0,0 +1,70 @@
+//! Shapes built by the derive from parts only known at runtime, Eg: the shape of a field that is a [Type::Ref]
+
+use indexmap::IndexMap;
+
+use crate::{Object, Property, Type};
+
+/// The fields of an internally tagged variant with its tag property.
+/// Exact objects can't be intersected, any extra key of one is a key of the other, so the tag is merged into them
+pub fn tagged(tag: String, property: Property, fields: Type) -> Type {
+  let merge = |object: Object| {
+    let mut properties = IndexMap::from([(tag.clone(), property.clone())]);
+    properties.extend(object.properties);
+    Type::Object(Object { properties, exact: true })
+  };
+
+  match fields {
+    Type::Object(object) if object.exact => merge(object),
+    Type::Or(alternatives) if alternatives.iter().all(|ty| matches!(ty, Type::Object(object) if object.exact)) => Type::Or(
+      alternatives
+        .into_iter()
+        .map(|ty| match ty {
+          Type::Object(object) => merge(object),
+          ty => ty,
+        })
+        .collect(),
+    ),
+    fields => Type::And(vec![
+      Type::Object(Object { properties: IndexMap::from([(tag, property)]), exact: false }),
+      fields,
+    ]),
+  }
+}
+
+/// An object with fields that can be deserialized under any of their aliases.
+/// `aliased` has the keys of each aliased field, Eg: `[("fullName", p), ("name", p)]`.
+/// An open object is intersected with a union of one key objects per field,
+/// an exact object is a union of exact objects, one for each choice of keys
+pub fn aliased(properties: IndexMap<String, Property>, aliased: Vec<Vec<(String, Property)>>, exact: bool) -> Type {
+  if aliased.is_empty() {
+    return Type::Object(Object { properties, exact });
+  }
+
+  if !exact {
+    let mut types = vec![Type::Object(Object { properties, exact: false })];
+    for keys in aliased {
+      types.push(Type::Or(
+        keys
+          .into_iter()
+          .map(|(key, property)| Type::Object(Object { properties: IndexMap::from([(key, property)]), exact: false }))
+          .collect(),
+      ));
+    }
+    return Type::And(types);
+  }
+
+  let mut alternatives = vec![properties];
+  for keys in aliased {
+    alternatives = alternatives
+      .into_iter()
+      .flat_map(|properties| {
+        keys.iter().map(move |(key, property)| {
+          let mut properties = properties.clone();
+          properties.insert(key.clone(), property.clone());
+          properties
+        })
+      })
+      .collect();
+  }
+  Type::Or(alternatives.into_iter().map(|properties| Type::Object(Object { properties, exact: true })).collect())
+}
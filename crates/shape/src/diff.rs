@@ -0,0 +1,267 @@
+use std::fmt;
+
+use crate::{
+  subtype::merge_objects,
+  to_json_schema::{accepts_undefined, without_undefined},
+  Literal, ShapeOptionsKind, ToTypescript, Type,
+};
+
+/// The changes between two versions of the shape of a type, see [diff]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diff {
+  pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+  /// Path to the change, `/key` for properties, `/*` for array items and record values,
+  /// `/0` for tuple items and `#Name` for union variants
+  pub path: String,
+  pub kind: ChangeKind,
+  pub breaking: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+  PropertyAdded { optional: bool },
+  PropertyRemoved { optional: bool },
+  PropertyMadeOptional,
+  PropertyMadeRequired,
+  VariantAdded,
+  VariantRemoved,
+  /// the object now rejects unknown keys
+  ObjectMadeExact,
+  /// the object now accepts unknown keys
+  ObjectMadeOpen,
+  TypeChanged { old: Type, new: Type },
+}
+
+/// Compares an old and a new version of the shape of the same type
+///
+/// Whether a change is breaking depends on `kind`:
+/// - for [ShapeOptionsKind::Serialize] shapes, consumers built for the old shape must accept every new value,
+///   so the new shape must be a subtype of the old one. Eg: adding a variant is breaking
+/// - for [ShapeOptionsKind::Deserialize] shapes, producers built for the old shape must still be accepted,
+///   so the old shape must be a subtype of the new one. Eg: adding an optional property is safe, adding a required one is breaking
+///
+/// Removing a variant is breaking for both, consumers of serialize shapes handle the variants they were built for,
+/// Eg: a client that shows a screen for each event loses one
+pub fn diff(old: &Type, new: &Type, kind: ShapeOptionsKind) -> Diff {
+  let mut differ = Differ { kind, changes: vec![] };
+  differ.diff(old, new, "");
+  Diff { changes: differ.changes }
+}
+
+impl Diff {
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  pub fn is_breaking(&self) -> bool {
+    self.changes.iter().any(|change| change.breaking)
+  }
+
+  pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+    self.changes.iter().filter(|change| change.breaking)
+  }
+}
+
+impl fmt::Display for ChangeKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ChangeKind::PropertyAdded { optional: true } => write!(f, "optional property added"),
+      ChangeKind::PropertyAdded { optional: false } => write!(f, "required property added"),
+      ChangeKind::PropertyRemoved { optional: true } => write!(f, "optional property removed"),
+      ChangeKind::PropertyRemoved { optional: false } => write!(f, "required property removed"),
+      ChangeKind::PropertyMadeOptional => write!(f, "property made optional"),
+      ChangeKind::PropertyMadeRequired => write!(f, "property made required"),
+      ChangeKind::VariantAdded => write!(f, "variant added"),
+      ChangeKind::VariantRemoved => write!(f, "variant removed"),
+      ChangeKind::ObjectMadeExact => write!(f, "object made exact"),
+      ChangeKind::ObjectMadeOpen => write!(f, "object made open"),
+      ChangeKind::TypeChanged { old, new } => write!(f, "type changed from {} to {}", old.to_typescript(), new.to_typescript()),
+    }
+  }
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let path = if self.path.is_empty() { "/" } else { &self.path };
+    let label = if self.breaking { "breaking" } else { "non-breaking" };
+    write!(f, "[{}] {}: {}", label, path, self.kind)
+  }
+}
+
+impl fmt::Display for Diff {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for change in self.changes.iter() {
+      writeln!(f, "{}", change)?;
+    }
+    Ok(())
+  }
+}
+
+struct Differ {
+  kind: ShapeOptionsKind,
+  changes: Vec<Change>,
+}
+
+impl Differ {
+  fn push(&mut self, path: &str, kind: ChangeKind, breaking: bool) {
+    self.changes.push(Change { path: path.to_string(), kind, breaking })
+  }
+
+  fn is_serialize(&self) -> bool {
+    matches!(self.kind, ShapeOptionsKind::Serialize)
+  }
+
+  fn diff(&mut self, old: &Type, new: &Type, path: &str) {
+    if old == new {
+      return;
+    }
+
+    match (old, new) {
+      (Type::Object(a), Type::Object(b)) => {
+        if !a.exact && b.exact {
+          self.push(path, ChangeKind::ObjectMadeExact, !self.is_serialize());
+        } else if a.exact && !b.exact {
+          self.push(path, ChangeKind::ObjectMadeOpen, self.is_serialize());
+        }
+
+        for (key, prop) in a.properties.iter() {
+          let path = format!("{}/{}", path, key);
+          let old_optional = prop.optional || accepts_undefined(&prop.ty);
+          match b.properties.get(key) {
+            None => {
+              // consumers may require it, producers sending it are only fine with open objects
+              let breaking = if self.is_serialize() { !old_optional } else { b.exact };
+              self.push(&path, ChangeKind::PropertyRemoved { optional: old_optional }, breaking);
+            }
+            Some(new_prop) => {
+              let new_optional = new_prop.optional || accepts_undefined(&new_prop.ty);
+              if old_optional && !new_optional {
+                self.push(&path, ChangeKind::PropertyMadeRequired, !self.is_serialize());
+              } else if !old_optional && new_optional {
+                self.push(&path, ChangeKind::PropertyMadeOptional, self.is_serialize());
+              }
+              self.diff(&present(&prop.ty), &present(&new_prop.ty), &path);
+            }
+          }
+        }
+
+        for (key, prop) in b.properties.iter() {
+          if !a.properties.contains_key(key) {
+            let optional = prop.optional || accepts_undefined(&prop.ty);
+            // consumers of an exact object reject new keys
+            let breaking = if self.is_serialize() { a.exact } else { !optional };
+            self.push(&format!("{}/{}", path, key), ChangeKind::PropertyAdded { optional }, breaking);
+          }
+        }
+      }
+
+      (Type::Array(a), Type::Array(b)) => self.diff(&a.item, &b.item, &format!("{}/*", path)),
+
+      (Type::Record(a), Type::Record(b)) if a.key == b.key && a.optional == b.optional => {
+        self.diff(&a.value, &b.value, &format!("{}/*", path))
+      }
+
+      (Type::Tuple(a), Type::Tuple(b)) if a.items.len() == b.items.len() && a.optional == b.optional && a.rest == b.rest => {
+        for (i, (a, b)) in a.items.iter().zip(b.items.iter()).enumerate() {
+          self.diff(a, b, &format!("{}/{}", path, i));
+        }
+      }
+
+      (Type::Or(a), Type::Or(b)) => self.variants(old, new, a, b, path),
+
+      // internally tagged variants and flattened structs
+      (Type::And(a), Type::And(b)) => match (merge_objects(a), merge_objects(b)) {
+        (Some(a), Some(b)) => self.diff(&Type::Object(a), &Type::Object(b), path),
+        _ => self.changed(old, new, path),
+      },
+
+      _ => self.changed(old, new, path),
+    }
+  }
+
+  fn changed(&mut self, old: &Type, new: &Type, path: &str) {
+    let breaking = if self.is_serialize() { !new.is_subtype_of(old) } else { !old.is_subtype_of(new) };
+    self.push(path, ChangeKind::TypeChanged { old: old.clone(), new: new.clone() }, breaking);
+  }
+
+  fn variants(&mut self, old: &Type, new: &Type, a: &[Type], b: &[Type], path: &str) {
+    let mut removed = a.iter().filter(|t| !b.contains(t)).collect::<Vec<&Type>>();
+    let mut added = b.iter().filter(|t| !a.contains(t)).collect::<Vec<&Type>>();
+
+    // variants with the same name are compared with each other
+    let mut i = 0;
+    while i < removed.len() {
+      let key = variant_key(removed[i]);
+      let position = key.as_ref().and_then(|key| added.iter().position(|t| variant_key(t).as_ref() == Some(key)));
+      match position {
+        Some(position) => {
+          let old_variant = removed.remove(i);
+          let new_variant = added.remove(position);
+          self.diff(old_variant, new_variant, &format!("{}#{}", path, key.unwrap()));
+        }
+        None => i += 1,
+      }
+    }
+
+    // a single unnamed variant changed, Eg: Option<T> to Option<U>
+    if removed.len() == 1 && added.len() == 1 && variant_key(removed[0]).is_none() && variant_key(added[0]).is_none() {
+      let new_variants = b.iter().filter(|t| *t != added[0]).collect::<Vec<_>>();
+      let old_variants = a.iter().filter(|t| *t != removed[0]).collect::<Vec<_>>();
+      if old_variants == new_variants {
+        self.diff(removed[0], added[0], path);
+        return;
+      }
+    }
+
+    if removed.iter().chain(added.iter()).any(|t| variant_key(t).is_none()) {
+      self.changed(old, new, path);
+      return;
+    }
+
+    for variant in removed {
+      let path = format!("{}#{}", path, variant_key(variant).unwrap());
+      self.push(&path, ChangeKind::VariantRemoved, true);
+    }
+
+    for variant in added {
+      let path = format!("{}#{}", path, variant_key(variant).unwrap());
+      self.push(&path, ChangeKind::VariantAdded, self.is_serialize());
+    }
+  }
+}
+
+/// The type of a property when it's present
+fn present(ty: &Type) -> Type {
+  match without_undefined(ty) {
+    Type::Or(mut types) if types.len() == 1 => types.pop().unwrap(),
+    ty => ty,
+  }
+}
+
+/// The name of an enum variant: the literal of unit variants, the single key of externally tagged variants
+/// or the value of the tag of internally and adjacently tagged variants
+fn variant_key(ty: &Type) -> Option<String> {
+  match ty {
+    Type::Literal(Literal::String(name)) => Some(name.clone()),
+    Type::Object(object) => {
+      let tag = object.properties.values().find_map(|prop| match &prop.ty {
+        Type::Literal(Literal::String(name)) if !prop.optional => Some(name.clone()),
+        _ => None,
+      });
+      match tag {
+        Some(tag) => Some(tag),
+        None if object.properties.len() == 1 => object.properties.keys().next().cloned(),
+        None => None,
+      }
+    }
+    Type::And(types) => types.iter().find_map(|t| match t {
+      Type::Object(_) => variant_key(t),
+      _ => None,
+    }),
+    _ => None,
+  }
+}
@@ -0,0 +1,392 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::{
+  Array, Definitions, Generic, Literal, Object, Property, Record, Shape, ShapeOptions, ShapeOptionsKind, Tuple, Type,
+};
+
+/// The version of the JSON format of [SchemaDocument], it changes when a document can't be read by an older version.
+/// A [Type] serialized on its own has no version, it uses the format of this version of the crate
+pub const DOCUMENT_VERSION: u64 = 1;
+
+/// The serialize and deserialize shapes of a set of named types, with a stable JSON format
+///
+/// This is what the `shape` command line tool reads, a program that links the rust types dumps it with [SchemaDocument::to_json]
+/// or serde, [Type] and its components serialize with the same format.
+/// Eg: `{ "version": 1, "serialize": [{ "name": "User", "params": [], "type": { "kind": "object", ... } }], "deserialize": [...] }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDocument {
+  pub serialize: Definitions,
+  pub deserialize: Definitions,
+}
+
+/// A document that can't be read, `path` is a JSON pointer to the invalid value
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentError {
+  pub path: String,
+  pub message: String,
+}
+
+impl fmt::Display for DocumentError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let path = if self.path.is_empty() { "/" } else { &self.path };
+    write!(f, "invalid schema document at {}: {}", path, self.message)
+  }
+}
+
+impl std::error::Error for DocumentError {}
+
+impl Default for SchemaDocument {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SchemaDocument {
+  pub fn new() -> Self {
+    Self {
+      serialize: Definitions::with_generics(),
+      deserialize: Definitions::with_generics(),
+    }
+  }
+
+  pub fn definitions(&self, kind: ShapeOptionsKind) -> &Definitions {
+    match kind {
+      ShapeOptionsKind::Serialize => &self.serialize,
+      ShapeOptionsKind::Deserialize => &self.deserialize,
+    }
+  }
+
+  /// Adds the serialize and deserialize shapes of `T` and every named type it uses,
+  /// if `T` is not a named type it is ignored, use [SchemaDocument::add_as] for those
+  pub fn add<T: Shape + ?Sized>(&mut self) -> &mut Self {
+    self.serialize.add::<T>(&ShapeOptions::for_serialize());
+    self.deserialize.add::<T>(&ShapeOptions::for_deserialize());
+    self
+  }
+
+  pub fn add_as<T: Shape + ?Sized>(&mut self, name: &str) -> &mut Self {
+    let ty = self.serialize.add::<T>(&ShapeOptions::for_serialize());
+    self.serialize.insert(name, vec![], ty);
+    let ty = self.deserialize.add::<T>(&ShapeOptions::for_deserialize());
+    self.deserialize.insert(name, vec![], ty);
+    self
+  }
+
+  /// Adds every type registered with `#[shape(export)]`
+  pub fn add_registered(&mut self) -> &mut Self {
+    for registered in crate::registered() {
+      (registered.shape_ref)(&ShapeOptions::for_serialize(), &mut self.serialize);
+      (registered.shape_ref)(&ShapeOptions::for_deserialize(), &mut self.deserialize);
+    }
+    self
+  }
+
+  pub fn to_json(&self) -> Value {
+    json!({
+      "version": DOCUMENT_VERSION,
+      "serialize": definitions_to_json(&self.serialize),
+      "deserialize": definitions_to_json(&self.deserialize),
+    })
+  }
+
+  pub fn from_json(value: &Value) -> Result<Self, DocumentError> {
+    let version = field(value, "", "version")?
+      .as_u64()
+      .ok_or_else(|| error("/version", "expected a number"))?;
+    if version > DOCUMENT_VERSION {
+      return Err(error(
+        "/version",
+        format!("version {} is newer than the supported version {}", version, DOCUMENT_VERSION),
+      ));
+    }
+
+    Ok(Self {
+      serialize: definitions_from_json(field(value, "", "serialize")?, "/serialize")?,
+      deserialize: definitions_from_json(field(value, "", "deserialize")?, "/deserialize")?,
+    })
+  }
+}
+
+fn error(path: &str, message: impl Into<String>) -> DocumentError {
+  DocumentError { path: path.to_string(), message: message.into() }
+}
+
+fn field<'a>(value: &'a Value, path: &str, key: &str) -> Result<&'a Value, DocumentError> {
+  match value {
+    Value::Object(map) => map.get(key).ok_or_else(|| error(path, format!("missing \"{}\"", key))),
+    _ => Err(error(path, "expected an object")),
+  }
+}
+
+fn string(value: &Value, path: &str, key: &str) -> Result<String, DocumentError> {
+  field(value, path, key)?
+    .as_str()
+    .map(String::from)
+    .ok_or_else(|| error(&format!("{}/{}", path, key), "expected a string"))
+}
+
+// fields added after the first version of the format are optional so older documents can still be read
+fn optional_string(value: &Value, path: &str, key: &str) -> Result<Option<String>, DocumentError> {
+  match field(value, path, key) {
+    Err(_) | Ok(Value::Null) => Ok(None),
+    Ok(Value::String(value)) => Ok(Some(value.clone())),
+    Ok(_) => Err(error(&format!("{}/{}", path, key), "expected a string")),
+  }
+}
+
+fn boolean(value: &Value, path: &str, key: &str) -> Result<bool, DocumentError> {
+  field(value, path, key)?
+    .as_bool()
+    .ok_or_else(|| error(&format!("{}/{}", path, key), "expected a boolean"))
+}
+
+fn array<'a>(value: &'a Value, path: &str, key: &str) -> Result<&'a Vec<Value>, DocumentError> {
+  field(value, path, key)?
+    .as_array()
+    .ok_or_else(|| error(&format!("{}/{}", path, key), "expected an array"))
+}
+
+fn types(value: &Value, path: &str, key: &str) -> Result<Vec<Type>, DocumentError> {
+  array(value, path, key)?
+    .iter()
+    .enumerate()
+    .map(|(i, item)| type_from_json(item, &format!("{}/{}/{}", path, key, i)))
+    .collect()
+}
+
+fn definitions_to_json(definitions: &Definitions) -> Value {
+  Value::Array(
+    definitions
+      .iter()
+      .map(|(name, ty)| {
+        let mut value = json!({
+          "name": name,
+          "params": definitions.params(name).unwrap_or(&[]),
+          "type": type_to_json(ty),
+        });
+        if let Some(description) = definitions.description(name) {
+          value["description"] = json!(description);
+        }
+        value
+      })
+      .collect(),
+  )
+}
+
+fn definitions_from_json(value: &Value, path: &str) -> Result<Definitions, DocumentError> {
+  let items = value.as_array().ok_or_else(|| error(path, "expected an array"))?;
+  let mut definitions = Definitions::with_generics();
+  for (i, item) in items.iter().enumerate() {
+    let path = format!("{}/{}", path, i);
+    let name = string(item, &path, "name")?;
+    let params = array(item, &path, "params")?
+      .iter()
+      .map(|param| param.as_str().map(String::from).ok_or_else(|| error(&format!("{}/params", path), "expected strings")))
+      .collect::<Result<Vec<String>, DocumentError>>()?;
+    let ty = type_from_json(field(item, &path, "type")?, &format!("{}/type", path))?;
+    if let Some(description) = optional_string(item, &path, "description")? {
+      definitions.describe(&name, description);
+    }
+    definitions.insert(name, params, ty);
+  }
+  Ok(definitions)
+}
+
+pub(crate) fn type_to_json(ty: &Type) -> Value {
+  match ty {
+    Type::Null => json!({ "kind": "null" }),
+    Type::Undefined => json!({ "kind": "undefined" }),
+    Type::Never => json!({ "kind": "never" }),
+    Type::Boolean => json!({ "kind": "boolean" }),
+    Type::Number => json!({ "kind": "number" }),
+    Type::String => json!({ "kind": "string" }),
+    Type::Literal(literal) => json!({ "kind": "literal", "value": literal_to_json(literal) }),
+    Type::Tuple(tuple) => tuple_to_json(tuple),
+    Type::Array(array) => array_to_json(array),
+    Type::Object(object) => object_to_json(object),
+    Type::Record(record) => record_to_json(record),
+    Type::And(types) => json!({ "kind": "and", "types": types.iter().map(type_to_json).collect::<Vec<Value>>() }),
+    Type::Or(types) => json!({ "kind": "or", "types": types.iter().map(type_to_json).collect::<Vec<Value>>() }),
+    Type::Custom(name) => json!({ "kind": "custom", "name": name }),
+    Type::Ref(name) => json!({ "kind": "ref", "name": name }),
+    Type::Param(name) => json!({ "kind": "param", "name": name }),
+    Type::Generic(generic) => generic_to_json(generic),
+  }
+}
+
+fn tuple_to_json(tuple: &Tuple) -> Value {
+  json!({
+    "kind": "tuple",
+    "items": tuple.items.iter().map(type_to_json).collect::<Vec<Value>>(),
+    "optional": tuple.optional,
+    "rest": tuple.rest.as_ref().map(|rest| type_to_json(rest)),
+  })
+}
+
+fn array_to_json(array: &Array) -> Value {
+  json!({ "kind": "array", "item": type_to_json(&array.item) })
+}
+
+// properties are a list, JSON objects don't keep the order of their keys
+fn object_to_json(object: &Object) -> Value {
+  json!({
+    "kind": "object",
+    "properties": object.properties.iter().map(|(name, prop)| {
+      let mut prop = property_to_json(prop);
+      prop["name"] = json!(name);
+      prop
+    }).collect::<Vec<Value>>(),
+    "exact": object.exact,
+  })
+}
+
+fn record_to_json(record: &Record) -> Value {
+  json!({
+    "kind": "record",
+    "optional": record.optional,
+    "readonly": record.readonly,
+    "key": type_to_json(&record.key),
+    "value": type_to_json(&record.value),
+  })
+}
+
+fn generic_to_json(generic: &Generic) -> Value {
+  json!({
+    "kind": "generic",
+    "name": generic.name,
+    "args": generic.args.iter().map(type_to_json).collect::<Vec<Value>>(),
+  })
+}
+
+fn literal_to_json(literal: &Literal) -> Value {
+  match literal {
+    Literal::String(value) => json!(value),
+    Literal::Number(value) => json!(value),
+    Literal::Boolean(value) => json!(value),
+  }
+}
+
+fn property_to_json(prop: &Property) -> Value {
+  let mut value = json!({ "optional": prop.optional, "readonly": prop.readonly, "type": type_to_json(&prop.ty) });
+  if let Some(description) = &prop.description {
+    value["description"] = json!(description);
+  }
+  value
+}
+
+pub(crate) fn type_from_json(value: &Value, path: &str) -> Result<Type, DocumentError> {
+  let kind = string(value, path, "kind")?;
+  let ty = match kind.as_str() {
+    "null" => Type::Null,
+    "undefined" => Type::Undefined,
+    "never" => Type::Never,
+    "boolean" => Type::Boolean,
+    "number" => Type::Number,
+    "string" => Type::String,
+    "literal" => Type::Literal(literal_from_json(field(value, path, "value")?, &format!("{}/value", path))?),
+    "tuple" => {
+      let items = types(value, path, "items")?;
+      let optional = field(value, path, "optional")?
+        .as_u64()
+        .filter(|optional| *optional as usize <= items.len())
+        .ok_or_else(|| error(&format!("{}/optional", path), "expected a number up to the number of items"))?;
+      let rest = match field(value, path, "rest")? {
+        Value::Null => None,
+        rest => Some(Box::new(type_from_json(rest, &format!("{}/rest", path))?)),
+      };
+      Type::Tuple(Tuple { items, optional: optional as usize, rest })
+    }
+    "array" => Type::Array(Array {
+      item: Box::new(type_from_json(field(value, path, "item")?, &format!("{}/item", path))?),
+    }),
+    "object" => {
+      let mut properties = IndexMap::new();
+      for (i, prop) in array(value, path, "properties")?.iter().enumerate() {
+        let path = format!("{}/properties/{}", path, i);
+        properties.insert(string(prop, &path, "name")?, property_from_json(prop, &path)?);
+      }
+      Type::Object(Object { properties, exact: boolean(value, path, "exact")? })
+    }
+    "record" => Type::Record(Record {
+      optional: boolean(value, path, "optional")?,
+      readonly: boolean(value, path, "readonly")?,
+      key: Box::new(type_from_json(field(value, path, "key")?, &format!("{}/key", path))?),
+      value: Box::new(type_from_json(field(value, path, "value")?, &format!("{}/value", path))?),
+    }),
+    "and" => Type::And(types(value, path, "types")?),
+    "or" => Type::Or(types(value, path, "types")?),
+    "custom" => Type::Custom(string(value, path, "name")?),
+    "ref" => Type::Ref(string(value, path, "name")?),
+    "param" => Type::Param(string(value, path, "name")?),
+    "generic" => Type::Generic(Generic { name: string(value, path, "name")?, args: types(value, path, "args")? }),
+    kind => return Err(error(&format!("{}/kind", path), format!("unknown kind \"{}\"", kind))),
+  };
+  Ok(ty)
+}
+
+fn literal_from_json(value: &Value, path: &str) -> Result<Literal, DocumentError> {
+  match value {
+    Value::String(value) => Ok(Literal::String(value.clone())),
+    Value::Bool(value) => Ok(Literal::Boolean(*value)),
+    Value::Number(value) => Ok(Literal::Number(value.as_f64().ok_or_else(|| error(path, "number out of range"))?)),
+    _ => Err(error(path, "expected a string, number or boolean")),
+  }
+}
+
+fn property_from_json(value: &Value, path: &str) -> Result<Property, DocumentError> {
+  Ok(Property {
+    optional: boolean(value, path, "optional")?,
+    readonly: boolean(value, path, "readonly")?,
+    ty: type_from_json(field(value, path, "type")?, &format!("{}/type", path))?,
+    description: optional_string(value, path, "description")?,
+  })
+}
+
+// serde goes through the same JSON so `serde_json::to_string(&ty)` and `SchemaDocument::to_json` always agree,
+// the components are written like the type that holds them, a literal is its bare value and a property has no name
+macro_rules! json_serde {
+  ($ty:ty, $to_json:expr, $from_json:expr) => {
+    impl Serialize for $ty {
+      fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[allow(clippy::redundant_closure_call)]
+        ($to_json)(self).serialize(serializer)
+      }
+    }
+
+    impl<'de> Deserialize<'de> for $ty {
+      fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        #[allow(clippy::redundant_closure_call)]
+        ($from_json)(&value).map_err(D::Error::custom)
+      }
+    }
+  };
+}
+
+macro_rules! json_serde_variant {
+  ($ty:ident, $to_json:ident, $kind:literal) => {
+    json_serde!(
+      $ty,
+      $to_json,
+      |value: &Value| match type_from_json(value, "")? {
+        Type::$ty(value) => Ok(value),
+        _ => Err(error("/kind", concat!("expected \"", $kind, "\""))),
+      }
+    );
+  };
+}
+
+json_serde!(Type, type_to_json, |value| type_from_json(value, ""));
+json_serde!(Literal, literal_to_json, |value| literal_from_json(value, ""));
+json_serde!(Property, property_to_json, |value| property_from_json(value, ""));
+json_serde_variant!(Tuple, tuple_to_json, "tuple");
+json_serde_variant!(Array, array_to_json, "array");
+json_serde_variant!(Object, object_to_json, "object");
+json_serde_variant!(Record, record_to_json, "record");
+json_serde_variant!(Generic, generic_to_json, "generic");
+json_serde!(Definitions, definitions_to_json, |value| definitions_from_json(value, ""));
+json_serde!(SchemaDocument, SchemaDocument::to_json, SchemaDocument::from_json);
@@ -0,0 +1,168 @@
+use indexmap::IndexMap;
+
+use crate::{
+  definitions::identifier, to_typescript::doc_comment, to_zod::describe, Definitions, Shape, ShapeOptions, ToTypescript,
+  ToZod, Type,
+};
+
+/// Collects a set of root types and every named type they use,
+/// and renders them as `export type Name = ...;` typescript declarations
+///
+/// References between named types are rendered by name instead of being inlined,
+/// generic types with #[shape(generic)] are declared once with their type parameters, Eg: `export type Page<T> = ...` used as `Page<User>`.
+/// Declarations are sorted by name so the output doesn't depend on the order the roots are added
+#[derive(Debug, Clone)]
+pub struct TypescriptExporter {
+  options: ShapeOptions,
+  definitions: Definitions,
+  // roots that are not named types by themselves, Eg: Vec<User>
+  aliases: IndexMap<String, Type>,
+}
+
+impl TypescriptExporter {
+  pub fn new(options: ShapeOptions) -> Self {
+    Self {
+      options,
+      definitions: Definitions::with_generics(),
+      aliases: IndexMap::new(),
+    }
+  }
+
+  /// Exports every type in `definitions`, Eg: the definitions of a [crate::SchemaDocument]
+  pub fn with_definitions(options: ShapeOptions, definitions: Definitions) -> Self {
+    Self {
+      options,
+      definitions,
+      aliases: IndexMap::new(),
+    }
+  }
+
+  pub fn options(&self) -> &ShapeOptions {
+    &self.options
+  }
+
+  pub fn definitions(&self) -> &Definitions {
+    &self.definitions
+  }
+
+  /// Adds `T` and every named type it uses to the export,
+  /// if `T` is not a named type (Eg: a derived struct or enum) it is ignored, use [TypescriptExporter::add_as] for those
+  pub fn add<T: Shape + ?Sized>(&mut self) -> &mut Self {
+    self.definitions.add::<T>(&self.options);
+    self.rename_aliases();
+    self
+  }
+
+  /// Adds every type registered with `#[shape(export)]`, see [crate::registered]
+  pub fn add_registered(&mut self) -> &mut Self {
+    for registered in crate::registered() {
+      (registered.shape_ref)(&self.options, &mut self.definitions);
+    }
+    self.rename_aliases();
+    self
+  }
+
+  /// Adds `T` exported with `name`, every named type used by `T` is added too
+  pub fn add_as<T: Shape + ?Sized>(&mut self, name: impl Into<String>) -> &mut Self {
+    let ty = self.definitions.add::<T>(&self.options);
+    self.aliases.insert(name.into(), ty);
+    self.rename_aliases();
+    self
+  }
+
+  /// Updates the references of the aliases to definitions renamed by a name collision
+  fn rename_aliases(&mut self) {
+    for ty in self.aliases.values_mut() {
+      *ty = self.definitions.renamed(ty);
+    }
+  }
+
+  /// Returns every declaration as (name, type) sorted by name
+  pub fn declarations(&self) -> Vec<(String, &Type)> {
+    let mut declarations = self
+      .definitions
+      .iter()
+      .map(|(name, ty)| (identifier(name), ty))
+      .chain(self.aliases.iter().map(|(name, ty)| (identifier(name), ty)))
+      .collect::<Vec<_>>();
+    declarations.sort_by(|a, b| a.0.cmp(&b.0));
+    declarations
+  }
+
+  /// The type parameters of a declaration, empty if it's not generic
+  fn params(&self, name: &str) -> &[String] {
+    self.definition(name).and_then(|definition| self.definitions.params(definition)).unwrap_or(&[])
+  }
+
+  /// The doc comment of a declaration, rendered above it
+  fn doc(&self, name: &str) -> String {
+    match self.definition(name).and_then(|definition| self.definitions.description(definition)) {
+      Some(description) => format!("{}\n", doc_comment(description)),
+      None => String::new(),
+    }
+  }
+
+  /// The definition of a declaration, aliases added with [TypescriptExporter::add_as] have none
+  fn definition(&self, name: &str) -> Option<&str> {
+    self.definitions.iter().find(|(definition, _)| identifier(definition) == name).map(|(definition, _)| definition)
+  }
+
+  pub fn export(&self) -> String {
+    let mut out = String::new();
+    for (name, ty) in self.declarations() {
+      out.push_str(&self.declaration(&name, ty));
+    }
+    out
+  }
+
+  /// Renders every declaration as a typescript type and a zod schema with the same name,
+  /// the schema is annotated with the type so recursive schemas type check
+  pub fn export_zod(&self) -> String {
+    let mut out = String::from("import { z } from \"zod\";\n");
+    for (name, ty) in self.declarations() {
+      out.push('\n');
+      out.push_str(&self.zod_declaration(&name, ty));
+    }
+    out
+  }
+
+  /// The `export type` of one of [TypescriptExporter::declarations]
+  pub(crate) fn declaration(&self, name: &str, ty: &Type) -> String {
+    let params = self.params(name);
+    if params.is_empty() {
+      format!("{}export type {} = {};\n", self.doc(name), name, ty.to_typescript())
+    } else {
+      format!("{}export type {}<{}> = {};\n", self.doc(name), name, params.join(", "), ty.to_typescript())
+    }
+  }
+
+  /// The `export type` and `export const` schema of one of [TypescriptExporter::declarations]
+  pub(crate) fn zod_declaration(&self, name: &str, ty: &Type) -> String {
+    let params = self.params(name);
+    let doc = self.doc(name);
+    let describe = self
+      .definition(name)
+      .and_then(|definition| self.definitions.description(definition))
+      .map(describe)
+      .unwrap_or_default();
+    if params.is_empty() {
+      format!(
+        "{doc}export type {name} = {};\nexport const {name}: z.ZodType<{name}> = {}{describe};\n",
+        ty.to_typescript(),
+        self.definitions.resolve_keys(ty).to_zod()
+      )
+    } else {
+      // generic schemas are functions taking a schema for each type parameter
+      let bounds = params.iter().map(|param| format!("{param} extends z.ZodTypeAny")).collect::<Vec<String>>();
+      let args = params.iter().map(|param| format!("{param}: {param}")).collect::<Vec<String>>();
+      format!(
+        "{doc}export type {name}<{}> = {};\nexport const {name} = <{}>({}) => {}{describe};\n",
+        params.join(", "),
+        ty.to_typescript(),
+        bounds.join(", "),
+        args.join(", "),
+        self.definitions.resolve_keys(ty).to_zod()
+      )
+    }
+  }
+}
@@ -1,39 +1,75 @@
-use serde_json::{json, Map, Value};
+use serde_json::{Map, Value};
 
-use crate::{Array, Literal, Object, Record, Tuple, Type};
+use crate::{to_json_schema::accepts_undefined, validate::key_matches, Array, Definitions, Literal, Object, Record, Tuple, Type};
 
 pub trait IsAsignable {
-  fn is_assignable(&self, v: &Value) -> bool;  
+  fn is_assignable(&self, v: &Value) -> bool;
+
+  /// Like [IsAsignable::is_assignable] but [Type::Ref]s are resolved with `definitions`, needed for recursive types
+  fn is_assignable_with(&self, v: &Value, definitions: &Definitions) -> bool;
+}
+
+/// Checks values, references are resolved with `definitions` if there are any
+struct Assignable<'a> {
+  definitions: Option<&'a Definitions>,
+}
+
+macro_rules! impl_assignable {
+  ($ty:ty, $method:ident) => {
+    impl IsAsignable for $ty {
+      fn is_assignable(&self, v: &Value) -> bool {
+        Assignable { definitions: None }.$method(self, v)
+      }
+
+      fn is_assignable_with(&self, v: &Value, definitions: &Definitions) -> bool {
+        Assignable { definitions: Some(definitions) }.$method(self, v)
+      }
+    }
+  };
 }
 
-impl IsAsignable for Literal {
-  fn is_assignable(&self, v: &Value) -> bool {
-      match (self, v) {
-        (Self::String(l), Value::String(v)) => l == v,
-        (Self::Number(l), Value::Number(v)) => match v.as_f64() {
+impl_assignable!(Literal, literal);
+impl_assignable!(Tuple, tuple);
+impl_assignable!(Array, array);
+impl_assignable!(Object, object);
+impl_assignable!(Record, record);
+impl_assignable!(Type, check);
+
+impl Assignable<'_> {
+  fn literal(&self, literal: &Literal, v: &Value) -> bool {
+      match (literal, v) {
+        (Literal::String(l), Value::String(v)) => l == v,
+        (Literal::Number(l), Value::Number(v)) => match v.as_f64() {
           Some(v) => *l == v,
           None => false,
         },
-        (Self::Boolean(l), Value::Bool(v)) => l == v,
+        (Literal::Boolean(l), Value::Bool(v)) => l == v,
         _ => false,
       }
   }
-}
 
-impl IsAsignable for Tuple {
-  fn is_assignable(&self, v: &Value) -> bool {
+  fn tuple(&self, tuple: &Tuple, v: &Value) -> bool {
     match v {
       Value::Array(items) => {
-        for (i, t) in self.items.iter().enumerate() {
-          if !t.is_assignable(&items[i]) {
-            return false;
+        for (i, t) in tuple.items.iter().enumerate() {
+          match items.get(i) {
+            Some(item) => {
+              if !self.check(t, item) {
+                return false;
+              }
+            }
+            None => {
+              if i < tuple.required() && !accepts_undefined(t) {
+                return false;
+              }
+            }
           }
         }
-        
-        match &self.rest {
-          None => true,
+
+        match &tuple.rest {
+          None => items.len() <= tuple.items.len(),
           Some(rest) => {
-            items.iter().skip(self.items.len()).all(|item| rest.is_assignable(item))  
+            items.iter().skip(tuple.items.len()).all(|item| self.check(rest, item))
           }
         }
 
@@ -41,121 +77,96 @@ impl IsAsignable for Tuple {
       _ => false,
     }
   }
-}
 
-impl IsAsignable for Array {
-  fn is_assignable(&self, v: &Value) -> bool {
+  fn array(&self, array: &Array, v: &Value) -> bool {
     match v {
       Value::Array(items) => {
-        items.iter().all(|item| self.item.is_assignable(item))
+        items.iter().all(|item| self.check(&array.item, item))
       }
       _ => false,
     }
   }
-}
 
-impl IsAsignable for Object {
-  fn is_assignable(&self, v: &Value) -> bool {
+  fn object(&self, object: &Object, v: &Value) -> bool {
     match v {
       Value::Object(map) => {
-        self.properties.iter().all(|(key, prop)| {
+        if object.exact && map.keys().any(|key| !object.properties.contains_key(key)) {
+          return false;
+        }
+
+        object.properties.iter().all(|(key, prop)| {
           let v = map.get(key);
           match v {
             None => prop.optional,
-            Some(v) => prop.ty.is_assignable(v),
+            Some(v) => self.check(&prop.ty, v),
           }
         })
       },
       _ => false,
     }
   }
-}
 
-impl IsAsignable for Record {
-  fn is_assignable(&self, v: &Value) -> bool {
+  fn record(&self, record: &Record, v: &Value) -> bool {
     match v {
       Value::Object(map) => {
-        // A partial struct is assignable to all structs
-        if self.optional {
-          return true
-        }
-
-        fn match_key(key: &Type, ty: &Type, map: &Map<String, Value>) -> bool { 
-          match key {
-            Type::Null => false,
-            Type::Undefined => false,
-            Type::Boolean => false,
-            Type::Tuple(_) => false,
-            Type::Array(_) => false,
-            Type::Object(_) => false,
-            Type::Record(_) => false,
-            Type::Custom(_) => false,
-            Type::Never => false,
-            Type::String => {
-              map.iter().all(|(_, v)| {
-                ty.is_assignable(v)
-              })
-            },
-            Type::Number => {
-              map.iter().all(|(k, v)| {
-                use std::str::FromStr;
-                match f64::from_str(k) {
-                  Err(_) => false,
-                  Ok(_) => ty.is_assignable(v)
-                }
-              })
-            },
-            Type::And(iter) => {
-              map.iter().all(|(k, v)| {
-                if iter.iter().all(|k_each| k_each.is_assignable(&json!(k))) {
-                  ty.is_assignable(v)
-                } else {
-                  true
-                }
-              })
-            },
-            Type::Or(iter) => iter.iter().all(|k| match_key(k, ty, map)),
-            Type::Literal(lit) => match lit {
-              Literal::Boolean(_) => false,
-              Literal::String(s) => {
-                map.iter().any(|(k, v)| {
-                  s == k && ty.is_assignable(v)
-                })
-              }
-              Literal::Number(n) => {
-                use std::str::FromStr;
-                map.iter().any(|(k, v)| {
-                  f64::from_str(k) == Ok(*n) && ty.is_assignable(v)
-                })
-              }
-            }
-          }
-        }
-
-        match_key(&self.key, &self.value, map)        
+        let key = match self.definitions {
+          Some(definitions) => definitions.resolve(&record.key),
+          None => (*record.key).clone(),
+        };
+        is_key(&key)
+          && map.iter().all(|(k, v)| key_matches(&key, k, None) && self.check(&record.value, v))
+          // the keys of a partial record may be missing
+          && (record.optional || has_required_keys(&key, &record.value, map))
       },
       _ => false,
     }
   }
-}
 
-impl IsAsignable for Type {
-  fn is_assignable(&self, v: &Value) -> bool {
-    match self {
+  fn check(&self, ty: &Type, v: &Value) -> bool {
+    match ty {
       Type::String => v.is_string(),
       Type::Number => v.is_number(),
       Type::Boolean => v.is_boolean(),
       Type::Null => v.is_null(),
       Type::Undefined => false,
       Type::Never => false,
-      Type::Literal(literal) => literal.is_assignable(v),
-      Type::Tuple(tuple) => tuple.is_assignable(v),
-      Type::Array(array) => array.is_assignable(v),
-      Type::Object(object) => object.is_assignable(v),
-      Type::Record(record) => record.is_assignable(v),
-      Type::And(types) => types.iter().all(|t| t.is_assignable(v)),
-      Type::Or(types) => types.iter().any(|t| t.is_assignable(v)),
+      Type::Literal(literal) => self.literal(literal, v),
+      Type::Tuple(tuple) => self.tuple(tuple, v),
+      Type::Array(array) => self.array(array, v),
+      Type::Object(object) => self.object(object, v),
+      Type::Record(record) => self.record(record, v),
+      Type::And(types) => types.iter().all(|t| self.check(t, v)),
+      Type::Or(types) => types.iter().any(|t| self.check(t, v)),
       Type::Custom(_) => false,
+      // references can't be resolved without their definitions
+      Type::Ref(name) => match self.definitions.and_then(|definitions| definitions.get(name)) {
+        Some(resolved) => self.check(resolved, v),
+        None => false,
+      },
+      Type::Generic(generic) => match self.definitions.and_then(|definitions| definitions.instantiate(&generic.name, &generic.args)) {
+        Some(resolved) => self.check(&resolved, v),
+        None => false,
+      },
+      Type::Param(_) => false,
     }
   }
-}
\ No newline at end of file
+}
+
+/// Only strings, numbers, booleans and their literals can be checked as keys
+fn is_key(ty: &Type) -> bool {
+  match ty {
+    Type::String | Type::Number | Type::Boolean | Type::Literal(_) => true,
+    Type::Or(types) | Type::And(types) => types.iter().all(is_key),
+    _ => false,
+  }
+}
+
+/// Literal keys of a non partial record must be present, a boolean key is `true` and `false`
+fn has_required_keys(key: &Type, value: &Type, map: &Map<String, Value>) -> bool {
+  match key {
+    Type::Literal(_) => accepts_undefined(value) || map.keys().any(|k| key_matches(key, k, None)),
+    Type::Boolean => accepts_undefined(value) || (map.contains_key("true") && map.contains_key("false")),
+    Type::Or(types) => types.iter().all(|t| has_required_keys(t, value, map)),
+    _ => true,
+  }
+}
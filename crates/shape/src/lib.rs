@@ -1,11 +1,40 @@
 pub use shape_macros::Shape;
 
 mod to_typescript;
+mod to_json_schema;
+mod to_zod;
 mod is_assignable;
+mod definitions;
+mod validate;
+mod subtype;
+mod diff;
+mod export;
+mod openapi;
+mod lock;
+mod simplify;
+mod registry;
+mod document;
+pub mod build;
+#[doc(hidden)]
+pub mod skip_if;
+#[doc(hidden)]
+pub mod derive;
 
 pub use to_typescript::ToTypescript;
+pub use to_zod::ToZod;
+pub use to_json_schema::{json_schema, json_schema_definitions, json_schema_document, ToJsonSchema, JSON_SCHEMA_DIALECT};
 pub use indexmap;
 pub use is_assignable::IsAsignable;
+pub use definitions::Definitions;
+pub use validate::{Validate, ValidationError};
+pub use diff::{diff, Change, ChangeKind, Diff};
+pub use export::TypescriptExporter;
+pub use openapi::{OpenApi, OperationBuilder, OPENAPI_VERSION};
+pub use lock::{line_diff, LockError, SchemaLock, UPDATE_LOCK_ENV};
+pub use registry::{registered, Registered};
+pub use document::{DocumentError, SchemaDocument, DOCUMENT_VERSION};
+#[doc(hidden)]
+pub use inventory;
 
 use indexmap::IndexMap;
 use std::{
@@ -14,7 +43,19 @@ use std::{
 
 /// The shape trait is derived in a type to generate a schema for the (de)serialization of that type
 pub trait Shape {
+  /// True if a missing value deserializes as `None`, so a field of this type can be absent. Eg: `Option<T>`
+  const IS_OPTION: bool = false;
+
+  /// The shape of the type with its fields inlined.
+  /// A recursive type refers to itself with a [Type::Ref] that this type doesn't define, use [Definitions::add] and
+  /// the `_with` methods to check values against it, Eg: [IsAsignable::is_assignable_with] and [Validate::validate_with]
   fn shape(options: &ShapeOptions) -> Type;
+
+  /// Like [Shape::shape] but named types are registered in `definitions` and referenced with [Type::Ref] instead of being inlined.
+  /// This is what allows recursive types to be described
+  fn shape_ref(options: &ShapeOptions, _definitions: &mut Definitions) -> Type {
+    Self::shape(options)
+  }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -77,14 +118,35 @@ pub enum Type {
   Or(Vec<Type>),
   /// a way to declare a custom type Eg: #\[shape(type = "Date")\]
   Custom(String),
+  /// a reference to a named type registered in a [Definitions] table
+  Ref(String),
+  /// a type parameter of a generic definition, Eg: the `T` in `Page<T>`
+  Param(String),
+  /// a reference to a generic definition with its type arguments, Eg: `Page<User>`
+  Generic(Generic),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generic {
+  pub name: String,
+  pub args: Vec<Type>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Tuple {
   pub items: Vec<Type>,
+  /// the last `optional` items may be missing, Eg: `[A, B?, C?]` has 2
+  pub optional: usize,
   pub rest: Option<Box<Type>>,
 }
 
+impl Tuple {
+  /// The number of items that must be present, `optional` larger than `items` means none
+  pub fn required(&self) -> usize {
+    self.items.len().saturating_sub(self.optional)
+  }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Array {
   pub item: Box<Type>,
@@ -93,6 +155,8 @@ pub struct Array {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object {
   pub properties: IndexMap<String, Property>,
+  /// keys not in `properties` are rejected, Eg: #\[serde(deny_unknown_fields)\]
+  pub exact: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -108,6 +172,8 @@ pub struct Property {
   pub optional: bool,
   pub readonly: bool,
   pub ty: Type,
+  /// the `///` doc comment of the field or variant
+  pub description: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -151,9 +217,15 @@ impl_ty!(std::net::Ipv4Addr, Type::String);
 impl_ty!(std::net::Ipv6Addr, Type::String);
 
 impl<T: Shape + ToOwned> Shape for Cow<'_, T> {
+  const IS_OPTION: bool = T::IS_OPTION;
+
   fn shape(options: &ShapeOptions) -> Type {
     T::shape(options)
   }
+
+  fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+    T::shape_ref(options, definitions)
+  }
 }
 
 
@@ -175,23 +247,38 @@ impl<T> Shape for ordered_float::OrderedFloat<T> {
 
 
 impl<T: Shape + ?Sized> Shape for &T {
+  const IS_OPTION: bool = T::IS_OPTION;
+
   fn shape(options: &ShapeOptions) -> Type {
     T::shape(options)
   }
+
+  fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+    T::shape_ref(options, definitions)
+  }
+}
+
+fn option(inner: Type, options: &ShapeOptions) -> Type {
+  if options.option_add_null && options.option_add_undefined {
+    Type::Or(vec![inner, Type::Null, Type::Undefined])
+  } else if options.option_add_null {
+    Type::Or(vec![inner, Type::Null])
+  } else if options.option_add_undefined {
+    Type::Or(vec![inner, Type::Undefined])
+  } else {
+    inner
+  }
 }
 
 impl<T: Shape> Shape for Option<T> {
+  const IS_OPTION: bool = true;
+
   fn shape(options: &ShapeOptions) -> Type {
-    let inner = T::shape(options);
-    if options.option_add_null && options.option_add_undefined {
-      Type::Or(vec![inner, Type::Null, Type::Undefined])
-    } else if options.option_add_null {
-      Type::Or(vec![inner, Type::Null])
-    } else if options.option_add_undefined {
-      Type::Or(vec![inner, Type::Undefined])
-    } else {
-      inner
-    }
+    option(T::shape(options), options)
+  }
+
+  fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+    option(T::shape_ref(options, definitions), options)
   }
 }
 
@@ -201,9 +288,15 @@ macro_rules! impl_inner {
     where
       $inner: Shape + ?Sized,
     {
+      const IS_OPTION: bool = <$inner>::IS_OPTION;
+
       fn shape(options: &ShapeOptions) -> Type {
         <$inner>::shape(options)
       }
+
+      fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+        <$inner>::shape_ref(options, definitions)
+      }
     }
   };
 }
@@ -223,6 +316,12 @@ macro_rules! impl_slice {
           item: Box::new(<$inner>::shape(options)),
         })
       }
+
+      fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+        Type::Array(Array {
+          item: Box::new(<$inner>::shape_ref(options, definitions)),
+        })
+      }
     }
   };
 }
@@ -235,17 +334,26 @@ impl_slice!(T, impl<T: Shape> Shape for BTreeSet<T>);
 #[cfg(feature = "indexmap")]
 impl_slice!(T, impl<T: Shape> Shape for indexmap::IndexSet<T>);
 
+/// A map doesn't need to have every key, that only matters when the keys are a known set, Eg: an enum
+fn map_shape(key: Type, value: Type) -> Type {
+  Type::Record(Record {
+    optional: !matches!(key, Type::String | Type::Number),
+    readonly: false,
+    key: Box::new(key),
+    value: Box::new(value),
+  })
+}
+
 macro_rules! impl_map {
   ($k:ty, $v:ty, $($tt:tt)*) => {
     $($tt)*
     {
       fn shape(options: &ShapeOptions) -> Type {
-        Type::Record(Record {
-          optional: true,
-          readonly: false,
-          key: Box::new(<$k>::shape(options)),
-          value: Box::new(<$v>::shape(options)),
-        })
+        map_shape(<$k>::shape(options), <$v>::shape(options))
+      }
+
+      fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+        map_shape(<$k>::shape_ref(options, definitions), <$v>::shape_ref(options, definitions))
       }
     }
   };
@@ -270,6 +378,17 @@ macro_rules! impl_tuple {
           items: vec![
             $(<$ty>::shape(options)),*
           ],
+          optional: 0,
+          rest: None,
+        })
+      }
+
+      fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+        Type::Tuple(Tuple {
+          items: vec![
+            $(<$ty>::shape_ref(options, definitions)),*
+          ],
+          optional: 0,
           rest: None,
         })
       }
@@ -295,12 +414,11 @@ where
   T: Shape,
 {
   fn shape(options: &ShapeOptions) -> Type {
-    let inner = T::shape(options);
-    let mut items = Vec::with_capacity(N);
-    for _ in 0..N {
-      items.push(inner.clone());
-    }
-    Type::Tuple(Tuple { items, rest: None })
+    Type::Tuple(Tuple { items: vec![T::shape(options); N], optional: 0, rest: None })
+  }
+
+  fn shape_ref(options: &ShapeOptions, definitions: &mut Definitions) -> Type {
+    Type::Tuple(Tuple { items: vec![T::shape_ref(options, definitions); N], optional: 0, rest: None })
   }
 }
 
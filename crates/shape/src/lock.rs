@@ -0,0 +1,185 @@
+use std::{fmt, io, path::Path};
+
+use crate::{SchemaDocument, Shape, ShapeOptions, TypescriptExporter};
+
+/// The environment variable that makes [SchemaLock::verify] update the lock file instead of checking it
+pub const UPDATE_LOCK_ENV: &str = "SHAPE_UPDATE_LOCK";
+
+/// A committed snapshot of the serialize and deserialize shapes of a set of types
+///
+/// The lock file is rendered as typescript declarations, so changes are easy to review.
+/// In CI, [SchemaLock::check] fails with a diff when the shapes no longer match the lock file
+#[derive(Debug, Clone)]
+pub struct SchemaLock {
+  serialize: TypescriptExporter,
+  deserialize: TypescriptExporter,
+}
+
+#[derive(Debug)]
+pub enum LockError {
+  Io(io::Error),
+  /// The lock file is missing or outdated, contains a line diff from the lock file to the current shapes
+  Mismatch { diff: String },
+}
+
+impl fmt::Display for LockError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LockError::Io(e) => write!(f, "error reading the lock file: {}", e),
+      LockError::Mismatch { diff } => write!(
+        f,
+        "the lock file doesn't match the current shapes, review the changes and run again with {}=1 to update it\n{}",
+        UPDATE_LOCK_ENV, diff
+      ),
+    }
+  }
+}
+
+impl std::error::Error for LockError {}
+
+impl From<io::Error> for LockError {
+  fn from(e: io::Error) -> Self {
+    LockError::Io(e)
+  }
+}
+
+impl Default for SchemaLock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SchemaLock {
+  pub fn new() -> Self {
+    Self::with_options(ShapeOptions::for_serialize(), ShapeOptions::for_deserialize())
+  }
+
+  pub fn with_options(serialize: ShapeOptions, deserialize: ShapeOptions) -> Self {
+    Self {
+      serialize: TypescriptExporter::new(serialize),
+      deserialize: TypescriptExporter::new(deserialize),
+    }
+  }
+
+  /// A lock of the shapes in a document, it renders the same as a lock with the same types added
+  pub fn from_document(document: &SchemaDocument) -> Self {
+    Self {
+      serialize: TypescriptExporter::with_definitions(ShapeOptions::for_serialize(), document.serialize.clone()),
+      deserialize: TypescriptExporter::with_definitions(ShapeOptions::for_deserialize(), document.deserialize.clone()),
+    }
+  }
+
+  /// Adds the serialize and deserialize shapes of `T` and every named type it uses
+  pub fn add<T: Shape + ?Sized>(&mut self) -> &mut Self {
+    self.serialize.add::<T>();
+    self.deserialize.add::<T>();
+    self
+  }
+
+  /// Adds every type registered with `#[shape(export)]`
+  pub fn add_registered(&mut self) -> &mut Self {
+    self.serialize.add_registered();
+    self.deserialize.add_registered();
+    self
+  }
+
+  pub fn add_as<T: Shape + ?Sized>(&mut self, name: &str) -> &mut Self {
+    self.serialize.add_as::<T>(name);
+    self.deserialize.add_as::<T>(name);
+    self
+  }
+
+  pub fn render(&self) -> String {
+    let mut out = String::from("// This file is generated by shape, do not edit it by hand\n");
+    for (namespace, exporter) in [("Serialize", &self.serialize), ("Deserialize", &self.deserialize)] {
+      out.push_str(&format!("\nexport namespace {} {{\n", namespace));
+      for line in exporter.export().lines() {
+        out.push_str(&format!("  {}\n", line));
+      }
+      out.push_str("}\n");
+    }
+    out
+  }
+
+  pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::write(path, self.render())
+  }
+
+  /// Compares the lock file at `path` with the current shapes
+  pub fn check(&self, path: impl AsRef<Path>) -> Result<(), LockError> {
+    let current = match std::fs::read_to_string(path) {
+      Ok(current) => current,
+      Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+      Err(e) => return Err(e.into()),
+    };
+
+    let expected = self.render();
+    if current == expected {
+      Ok(())
+    } else {
+      Err(LockError::Mismatch { diff: line_diff(&current, &expected) })
+    }
+  }
+
+  /// Writes the lock file if the [UPDATE_LOCK_ENV] environment variable is set, otherwise checks it
+  pub fn verify(&self, path: impl AsRef<Path>) -> Result<(), LockError> {
+    match std::env::var(UPDATE_LOCK_ENV) {
+      Ok(value) if !value.is_empty() && value != "0" => Ok(self.write(path)?),
+      _ => self.check(path),
+    }
+  }
+}
+
+/// A unified like line diff, lines only in `old` are prefixed with `-` and lines only in `new` with `+`
+pub fn line_diff(old: &str, new: &str) -> String {
+  let old = old.lines().collect::<Vec<&str>>();
+  let new = new.lines().collect::<Vec<&str>>();
+  if old == new {
+    return String::new();
+  }
+
+  let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+  let suffix = old[prefix..]
+    .iter()
+    .rev()
+    .zip(new[prefix..].iter().rev())
+    .take_while(|(a, b)| a == b)
+    .count();
+
+  let a = &old[prefix..old.len() - suffix];
+  let b = &new[prefix..new.len() - suffix];
+
+  // longest common subsequence of the changed region
+  let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+  for i in (0..a.len()).rev() {
+    for j in (0..b.len()).rev() {
+      lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+    }
+  }
+
+  let mut out = String::new();
+  if prefix > 0 {
+    out.push_str(&format!("  {}\n", old[prefix - 1]));
+  }
+
+  let (mut i, mut j) = (0, 0);
+  while i < a.len() || j < b.len() {
+    if i < a.len() && j < b.len() && a[i] == b[j] {
+      out.push_str(&format!("  {}\n", a[i]));
+      i += 1;
+      j += 1;
+    } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+      out.push_str(&format!("- {}\n", a[i]));
+      i += 1;
+    } else {
+      out.push_str(&format!("+ {}\n", b[j]));
+      j += 1;
+    }
+  }
+
+  if suffix > 0 {
+    out.push_str(&format!("  {}\n", old[old.len() - suffix]));
+  }
+
+  out
+}
@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::{
+  definitions::{collect_refs, identifier},
+  to_json_schema::described,
+  Definitions, Shape, ShapeOptions, ToJsonSchema, Type,
+};
+
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// Builds an OpenAPI 3.1 document
+///
+/// Request bodies use the deserialize shape of a type and responses use the serialize shape,
+/// named types are added to `components/schemas` and referenced with `$ref`.
+/// When the serialize and deserialize shapes of a named type differ, the deserialize one is suffixed with `Input`
+#[derive(Debug, Clone)]
+pub struct OpenApi {
+  title: String,
+  version: String,
+  serialize: ShapeOptions,
+  deserialize: ShapeOptions,
+  serialize_definitions: Definitions,
+  deserialize_definitions: Definitions,
+  // path => method => operation
+  paths: IndexMap<String, IndexMap<String, Operation>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Operation {
+  operation_id: Option<String>,
+  summary: Option<String>,
+  description: Option<String>,
+  tags: Vec<String>,
+  request_body: Option<Type>,
+  // status => (description, body)
+  responses: IndexMap<String, (String, Option<Type>)>,
+}
+
+/// Adds details to an operation created with [OpenApi::operation]
+pub struct OperationBuilder<'a> {
+  api: &'a mut OpenApi,
+  path: String,
+  method: String,
+}
+
+impl OpenApi {
+  pub fn new(title: impl Into<String>, version: impl Into<String>) -> Self {
+    Self {
+      title: title.into(),
+      version: version.into(),
+      serialize: ShapeOptions::for_serialize(),
+      deserialize: ShapeOptions::for_deserialize(),
+      serialize_definitions: Definitions::new(),
+      deserialize_definitions: Definitions::new(),
+      paths: IndexMap::new(),
+    }
+  }
+
+  /// Overrides the options used to compute the serialize and deserialize shapes
+  pub fn with_options(mut self, serialize: ShapeOptions, deserialize: ShapeOptions) -> Self {
+    self.serialize = serialize;
+    self.deserialize = deserialize;
+    self
+  }
+
+  /// Adds (or replaces) the operation for `method` and `path`, Eg: `api.operation("post", "/users")`
+  pub fn operation(&mut self, method: &str, path: &str) -> OperationBuilder<'_> {
+    let method = method.to_lowercase();
+    self
+      .paths
+      .entry(path.to_string())
+      .or_default()
+      .insert(method.clone(), Operation::default());
+
+    OperationBuilder {
+      api: self,
+      path: path.to_string(),
+      method,
+    }
+  }
+
+  /// Adds the serialize and deserialize shapes of `T` to `components/schemas` even if no operation uses it
+  pub fn component<T: Shape + ?Sized>(&mut self) -> &mut Self {
+    self.serialize_definitions.add::<T>(&self.serialize);
+    self.deserialize_definitions.add::<T>(&self.deserialize);
+    self
+  }
+
+  pub fn document(&self) -> Value {
+    let input_names = self.input_names();
+
+    // names in the `$ref`s are already identifiers
+    let serialize_ref = |name: &str| format!("#/components/schemas/{}", name);
+    let deserialize_ref = |name: &str| format!("#/components/schemas/{}", input_names[name]);
+
+    let mut schemas = Map::new();
+    for (name, ty) in self.serialize_definitions.iter() {
+      let description = self.serialize_definitions.description(name);
+      schemas.insert(identifier(name), described(schema(&self.serialize_definitions.resolve_keys(ty), &serialize_ref), description));
+    }
+    for (name, ty) in self.deserialize_definitions.iter() {
+      let input_name = &input_names[&identifier(name)];
+      if !schemas.contains_key(input_name) {
+        let description = self.deserialize_definitions.description(name);
+        schemas.insert(input_name.clone(), described(schema(&self.deserialize_definitions.resolve_keys(ty), &deserialize_ref), description));
+      }
+    }
+
+    let mut paths = Map::new();
+    for (path, operations) in self.paths.iter() {
+      let mut item = Map::new();
+      for (method, operation) in operations.iter() {
+        let mut op = Map::new();
+        if let Some(operation_id) = &operation.operation_id {
+          op.insert("operationId".into(), json!(operation_id));
+        }
+        if let Some(summary) = &operation.summary {
+          op.insert("summary".into(), json!(summary));
+        }
+        if let Some(description) = &operation.description {
+          op.insert("description".into(), json!(description));
+        }
+        if !operation.tags.is_empty() {
+          op.insert("tags".into(), json!(operation.tags));
+        }
+        if let Some(body) = &operation.request_body {
+          op.insert(
+            "requestBody".into(),
+            json!({
+              "required": true,
+              "content": {
+                "application/json": {
+                  "schema": schema(&self.deserialize_definitions.resolve_keys(&self.deserialize_definitions.renamed(body)), &deserialize_ref),
+                }
+              }
+            }),
+          );
+        }
+
+        let mut responses = Map::new();
+        for (status, (description, body)) in operation.responses.iter() {
+          let mut response = Map::new();
+          response.insert("description".into(), json!(description));
+          if let Some(body) = body {
+            response.insert(
+              "content".into(),
+              json!({
+                "application/json": {
+                  "schema": schema(&self.serialize_definitions.resolve_keys(&self.serialize_definitions.renamed(body)), &serialize_ref),
+                }
+              }),
+            );
+          }
+          responses.insert(status.clone(), Value::Object(response));
+        }
+        op.insert("responses".into(), Value::Object(responses));
+
+        item.insert(method.clone(), Value::Object(op));
+      }
+      paths.insert(path.clone(), Value::Object(item));
+    }
+
+    json!({
+      "openapi": OPENAPI_VERSION,
+      "info": {
+        "title": self.title,
+        "version": self.version,
+      },
+      "paths": paths,
+      "components": {
+        "schemas": schemas,
+      }
+    })
+  }
+
+  /// Component names of the deserialize definitions by identifier,
+  /// a definition keeps its name only if it's not used for serialize or equal to the serialize one,
+  /// and all the definitions it uses keep their names too
+  fn input_names(&self) -> IndexMap<String, String> {
+    let mut shared = self
+      .deserialize_definitions
+      .iter()
+      .filter(|(name, ty)| match self.serialize_definitions.get(name) {
+        None => true,
+        Some(serialize) => serialize == *ty,
+      })
+      .map(|(name, _)| name.to_string())
+      .collect::<HashSet<String>>();
+
+    loop {
+      let changed = shared
+        .iter()
+        .filter(|name| {
+          let mut refs = vec![];
+          collect_refs(self.deserialize_definitions.get(name).unwrap(), &mut refs);
+          refs.iter().any(|name| !shared.contains(*name))
+        })
+        .cloned()
+        .collect::<Vec<String>>();
+
+      if changed.is_empty() {
+        break;
+      }
+
+      for name in changed {
+        shared.remove(&name);
+      }
+    }
+
+    self
+      .deserialize_definitions
+      .iter()
+      .map(|(name, _)| {
+        let input_name = if shared.contains(name) {
+          identifier(name)
+        } else {
+          format!("{}Input", identifier(name))
+        };
+        (identifier(name), input_name)
+      })
+      .collect()
+  }
+}
+
+impl OperationBuilder<'_> {
+  fn operation(&mut self) -> &mut Operation {
+    &mut self.api.paths[&self.path][&self.method]
+  }
+
+  pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+    self.operation().operation_id = Some(operation_id.into());
+    self
+  }
+
+  pub fn summary(mut self, summary: impl Into<String>) -> Self {
+    self.operation().summary = Some(summary.into());
+    self
+  }
+
+  pub fn description(mut self, description: impl Into<String>) -> Self {
+    self.operation().description = Some(description.into());
+    self
+  }
+
+  pub fn tag(mut self, tag: impl Into<String>) -> Self {
+    self.operation().tags.push(tag.into());
+    self
+  }
+
+  /// Sets the JSON request body to the deserialize shape of `T`
+  pub fn request_body<T: Shape + ?Sized>(mut self) -> Self {
+    let ty = self.api.deserialize_definitions.add::<T>(&self.api.deserialize);
+    self.operation().request_body = Some(ty);
+    self
+  }
+
+  /// Adds a JSON response with the serialize shape of `T`
+  pub fn response<T: Shape + ?Sized>(mut self, status: u16, description: impl Into<String>) -> Self {
+    let ty = self.api.serialize_definitions.add::<T>(&self.api.serialize);
+    self.operation().responses.insert(status.to_string(), (description.into(), Some(ty)));
+    self
+  }
+
+  /// Adds a response without body
+  pub fn empty_response(mut self, status: u16, description: impl Into<String>) -> Self {
+    self.operation().responses.insert(status.to_string(), (description.into(), None));
+    self
+  }
+}
+
+/// JSON Schema of `ty` with its `$ref`s pointing to `components/schemas`
+fn schema(ty: &Type, reference: &dyn Fn(&str) -> String) -> Value {
+  fn rewrite(value: &mut Value, reference: &dyn Fn(&str) -> String) {
+    match value {
+      Value::Object(map) => {
+        for (key, value) in map.iter_mut() {
+          if key == "$ref" {
+            if let Value::String(target) = value {
+              if let Some(name) = target.strip_prefix("#/$defs/") {
+                *target = reference(name);
+              }
+            }
+          } else {
+            rewrite(value, reference);
+          }
+        }
+      }
+      Value::Array(items) => {
+        for item in items {
+          rewrite(item, reference);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  let mut value = ty.to_json_schema();
+  rewrite(&mut value, reference);
+  value
+}
@@ -0,0 +1,23 @@
+use crate::{Definitions, ShapeOptions, Type};
+
+/// A type registered with `#[shape(export)]`
+///
+/// Every registered type is collected at link time, so a new type can't be left out of the exported bindings.
+/// Eg: `TypescriptExporter::new(options).add_registered().export()`
+#[derive(Debug)]
+pub struct Registered {
+  /// the name of the rust type
+  pub name: &'static str,
+  pub module_path: &'static str,
+  /// [crate::Shape::shape_ref] of the type
+  pub shape_ref: fn(&ShapeOptions, &mut Definitions) -> Type,
+}
+
+inventory::collect!(Registered);
+
+/// Every type registered with `#[shape(export)]`, sorted by module path and name
+pub fn registered() -> Vec<&'static Registered> {
+  let mut registered = inventory::iter::<Registered>.into_iter().collect::<Vec<_>>();
+  registered.sort_by_key(|r| (r.module_path, r.name));
+  registered
+}
@@ -0,0 +1,132 @@
+use crate::{Array, Generic, Literal, Object, Record, Tuple, Type};
+
+impl Type {
+  /// Returns an equivalent type without the noise left by flatten and tagged enums
+  ///
+  /// Nested unions and intersections are flattened, duplicate members removed, `never` is dropped from unions,
+  /// intersections of plain objects are merged into one object and single member unions and intersections are unwrapped.
+  /// Eg: `(A | null) | undefined | never` becomes `A | null | undefined`
+  pub fn simplify(&self) -> Type {
+    match self {
+      Type::Tuple(tuple) => Type::Tuple(Tuple {
+        items: tuple.items.iter().map(Type::simplify).collect(),
+        rest: tuple.rest.as_ref().map(|rest| Box::new(rest.simplify())),
+        optional: tuple.optional,
+      }),
+      Type::Array(array) => Type::Array(Array { item: Box::new(array.item.simplify()) }),
+      Type::Object(object) => Type::Object(simplify_object(object)),
+      Type::Record(record) => Type::Record(Record {
+        optional: record.optional,
+        readonly: record.readonly,
+        key: Box::new(record.key.simplify()),
+        value: Box::new(record.value.simplify()),
+      }),
+      Type::Generic(generic) => Type::Generic(Generic {
+        name: generic.name.clone(),
+        args: generic.args.iter().map(Type::simplify).collect(),
+      }),
+      Type::Or(types) => simplify_or(types),
+      Type::And(types) => simplify_and(types),
+      _ => self.clone(),
+    }
+  }
+}
+
+fn simplify_object(object: &Object) -> Object {
+  let mut object = object.clone();
+  for prop in object.properties.values_mut() {
+    prop.ty = prop.ty.simplify();
+  }
+  object
+}
+
+fn is_never(ty: &Type) -> bool {
+  match ty {
+    Type::Never => true,
+    Type::And(types) => types.is_empty(),
+    _ => false,
+  }
+}
+
+fn is_top(ty: &Type) -> bool {
+  matches!(ty, Type::Custom(custom) if custom == "any" || custom == "unknown")
+}
+
+fn push_unique(types: &mut Vec<Type>, ty: Type) {
+  if !types.contains(&ty) {
+    types.push(ty)
+  }
+}
+
+fn simplify_or(types: &[Type]) -> Type {
+  let mut members = vec![];
+  for ty in types {
+    match ty.simplify() {
+      Type::Or(inner) => inner.into_iter().for_each(|t| push_unique(&mut members, t)),
+      ty if is_never(&ty) => {}
+      ty => push_unique(&mut members, ty),
+    }
+  }
+
+  if let Some(top) = members.iter().find(|t| is_top(t)) {
+    return top.clone();
+  }
+
+  // literals are absorbed by their base type
+  let snapshot = members.clone();
+  members.retain(|t| match t {
+    Type::Literal(Literal::String(_)) => !snapshot.contains(&Type::String),
+    Type::Literal(Literal::Number(_)) => !snapshot.contains(&Type::Number),
+    Type::Literal(Literal::Boolean(_)) => !snapshot.contains(&Type::Boolean),
+    _ => true,
+  });
+
+  match members.len() {
+    0 => Type::Never,
+    1 => members.pop().unwrap(),
+    _ => Type::Or(members),
+  }
+}
+
+fn simplify_and(types: &[Type]) -> Type {
+  // an empty intersection is never in every backend
+  if types.is_empty() {
+    return Type::Never;
+  }
+
+  let mut members = vec![];
+  let mut top = None;
+  for ty in types {
+    match ty.simplify() {
+      Type::And(inner) if !inner.is_empty() => inner.into_iter().for_each(|t| push_unique(&mut members, t)),
+      ty if is_never(&ty) => return Type::Never,
+      ty if is_top(&ty) => top = top.or(Some(ty)),
+      ty => push_unique(&mut members, ty),
+    }
+  }
+
+  // objects are merged into the first one while their keys don't conflict
+  let mut merged: Vec<Type> = vec![];
+  for ty in members {
+    let target = merged.iter_mut().find_map(|t| match (t, &ty) {
+      (Type::Object(target), Type::Object(object)) if can_merge(target, object) => Some(target),
+      _ => None,
+    });
+    match (target, ty) {
+      (Some(target), Type::Object(object)) => target.properties.extend(object.properties),
+      (_, ty) => merged.push(ty),
+    }
+  }
+
+  match merged.len() {
+    0 => top.unwrap(),
+    1 => merged.pop().unwrap(),
+    _ => Type::And(merged),
+  }
+}
+
+/// Repeated keys are only allowed with the same property,
+/// exact objects reject the keys of the other one so they are never merged
+fn can_merge(a: &Object, b: &Object) -> bool {
+  !a.exact && !b.exact && b.properties.iter().all(|(key, prop)| a.properties.get(key).map(|p| p == prop).unwrap_or(true))
+}
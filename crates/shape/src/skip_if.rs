@@ -0,0 +1,115 @@
+//! Serialize shapes of fields with a well known `skip_serializing_if` predicate, used by the derive.
+//! When the field is present the predicate was false, so the shape can be narrowed
+
+use std::collections::HashSet;
+
+use crate::{Literal, Tuple, Type};
+
+/// `Option::is_none`, a present value is never null
+pub fn none(ty: Type) -> Type {
+  match ty {
+    Type::Or(types) => {
+      let mut types = types.into_iter().filter(|t| !matches!(t, Type::Null)).collect::<Vec<Type>>();
+      if types.len() == 1 {
+        types.pop().unwrap()
+      } else {
+        Type::Or(types)
+      }
+    }
+    ty => ty,
+  }
+}
+
+/// `Vec::is_empty` and friends, a present array has at least one item
+pub fn empty(ty: Type) -> Type {
+  match ty {
+    Type::Array(array) => Type::Tuple(Tuple {
+      items: vec![(*array.item).clone()],
+      optional: 0,
+      rest: Some(array.item),
+    }),
+    ty => ty,
+  }
+}
+
+/// `is_false`, a present boolean is `true`, other types are kept
+pub fn false_(ty: Type) -> Type {
+  match ty {
+    Type::Boolean => Type::Literal(Literal::Boolean(true)),
+    ty => ty,
+  }
+}
+
+/// Tuples with more skippable fields get a looser shape instead of a union of up to 2^n tuples
+const MAX_SKIPPABLE: usize = 8;
+
+/// The shape of a tuple whose `fields` may be skipped, each field is its shape and whether it can be missing.
+/// A skipped field shifts the following ones, so `[A, B?, C?]` would be wrong when B is skipped and C is not,
+/// C would be checked as a B. The shape is a union with a tuple for each set of present fields,
+/// unless every one of them is a prefix of the full tuple, Eg: only the last field can be skipped gives `[A, B?]`.
+/// With more than [MAX_SKIPPABLE] skippable fields each item is the union of the fields that can be shifted to it,
+/// which also accepts tuples that serde can't produce
+pub fn tuple(fields: Vec<(Type, bool)>) -> Type {
+  let skippable = fields.iter().filter(|(_, skip)| *skip).count();
+  if skippable > MAX_SKIPPABLE {
+    return shifted(fields, skippable);
+  }
+
+  // the indexes of the present fields of each tuple
+  let mut variants: Vec<Vec<usize>> = vec![vec![]];
+  for (i, (_, skip)) in fields.iter().enumerate() {
+    if *skip {
+      let present = variants.iter().map(|present| [present.as_slice(), &[i]].concat()).collect::<Vec<_>>();
+      variants.extend(present);
+    } else {
+      variants.iter_mut().for_each(|present| present.push(i));
+    }
+  }
+
+  // fields of the same type share an id, so different fields giving the same tuple are deduped
+  let ids = fields
+    .iter()
+    .enumerate()
+    .map(|(i, (ty, _))| fields[..i].iter().position(|(other, _)| other == ty).unwrap_or(i))
+    .collect::<Vec<usize>>();
+  let mut seen = HashSet::new();
+  variants.retain(|present| seen.insert(present.iter().map(|i| ids[*i]).collect::<Vec<usize>>()));
+
+  let variants = variants
+    .into_iter()
+    .map(|present| present.into_iter().map(|i| fields[i].0.clone()).collect::<Vec<Type>>())
+    .collect::<Vec<_>>();
+
+  let full = variants.iter().max_by_key(|items| items.len()).cloned().unwrap_or_default();
+  if variants.iter().all(|items| full.starts_with(items)) {
+    let shortest = variants.iter().map(Vec::len).min().unwrap_or(0);
+    return Type::Tuple(Tuple { optional: full.len() - shortest, items: full, rest: None });
+  }
+
+  Type::Or(variants.into_iter().map(|items| Type::Tuple(Tuple { items, optional: 0, rest: None })).collect())
+}
+
+/// A field can be shifted to an earlier position by as many skippable fields as there are before it
+fn shifted(fields: Vec<(Type, bool)>, skippable: usize) -> Type {
+  let items = (0..fields.len())
+    .map(|position| {
+      let mut types = vec![];
+      let mut skippable = 0;
+      for (i, (ty, skip)) in fields.iter().enumerate() {
+        if i >= position && i - position <= skippable && !types.contains(ty) {
+          types.push(ty.clone());
+        }
+        if *skip {
+          skippable += 1;
+        }
+      }
+      if types.len() == 1 {
+        types.pop().unwrap()
+      } else {
+        Type::Or(types)
+      }
+    })
+    .collect();
+
+  Type::Tuple(Tuple { items, optional: skippable, rest: None })
+}
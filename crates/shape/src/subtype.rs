@@ -0,0 +1,324 @@
+use crate::{to_json_schema::accepts_undefined, validate::key_matches, Array, Definitions, Literal, Object, Record, ToTypescript, Tuple, Type};
+
+impl Type {
+  /// Returns true if every value of `self` is also a value of `other`
+  ///
+  /// Eg: the serialize shape of a server type should be a subtype of the deserialize shape of the client type.
+  /// The check is conservative, when it can't prove the relation it returns false
+  pub fn is_subtype_of(&self, other: &Type) -> bool {
+    Subtype { definitions: None, other_definitions: None, assumptions: vec![] }.check(self, other)
+  }
+
+  /// Like [Type::is_subtype_of] but [Type::Ref]s are resolved with the definitions of each side
+  pub fn is_subtype_of_with(&self, other: &Type, definitions: &Definitions, other_definitions: &Definitions) -> bool {
+    Subtype {
+      definitions: Some(definitions),
+      other_definitions: Some(other_definitions),
+      assumptions: vec![],
+    }
+    .check(self, other)
+  }
+}
+
+struct Subtype<'a> {
+  definitions: Option<&'a Definitions>,
+  other_definitions: Option<&'a Definitions>,
+  // pairs of references currently being checked, assumed true to end the recursion
+  assumptions: Vec<(String, String)>,
+}
+
+impl Subtype<'_> {
+  fn check(&mut self, sub: &Type, sup: &Type) -> bool {
+    if sub == sup && self.definitions.is_none() && self.other_definitions.is_none() {
+      return true;
+    }
+
+    match (sub, sup) {
+      (Type::Ref(a), Type::Ref(b)) => {
+        let pair = (a.clone(), b.clone());
+        if self.assumptions.contains(&pair) {
+          return true;
+        }
+        match (self.resolve(a), self.resolve_other(b)) {
+          (Some(a), Some(b)) => {
+            self.assumptions.push(pair);
+            let result = self.check(&a, &b);
+            self.assumptions.pop();
+            result
+          }
+          _ => a == b,
+        }
+      }
+      (Type::Ref(name), _) => match self.resolve(name) {
+        Some(resolved) => self.check(&resolved, sup),
+        None => false,
+      },
+      (_, Type::Ref(name)) => match self.resolve_other(name) {
+        Some(resolved) => self.check(sub, &resolved),
+        None => false,
+      },
+
+      (Type::Generic(a), Type::Generic(b)) => {
+        let pair = (sub.to_typescript(), sup.to_typescript());
+        if self.assumptions.contains(&pair) {
+          return true;
+        }
+        match (self.instantiate(&a.name, &a.args), self.instantiate_other(&b.name, &b.args)) {
+          (Some(a), Some(b)) => {
+            self.assumptions.push(pair);
+            let result = self.check(&a, &b);
+            self.assumptions.pop();
+            result
+          }
+          _ => sub == sup,
+        }
+      }
+      (Type::Generic(generic), _) => match self.instantiate(&generic.name, &generic.args) {
+        Some(resolved) => self.check(&resolved, sup),
+        None => false,
+      },
+      (_, Type::Generic(generic)) => match self.instantiate_other(&generic.name, &generic.args) {
+        Some(resolved) => self.check(sub, &resolved),
+        None => false,
+      },
+      (Type::Param(a), Type::Param(b)) => a == b,
+
+      // empty intersections are never, like in the other backends
+      (Type::Never, _) | (Type::And(_), _) if is_never(sub) => true,
+      (_, Type::And(types)) if types.is_empty() => self.check(sub, &Type::Never),
+      (_, Type::Custom(custom)) if custom == "any" || custom == "unknown" => true,
+
+      // distribution, every member of a union must be a subtype
+      (Type::Or(types), _) => types.iter().all(|t| self.check(t, sup)),
+      (_, Type::And(types)) => types.iter().all(|t| self.check(sub, t)),
+
+      (Type::And(types), _) => {
+        if let Some(object) = merge_objects(types) {
+          if types.len() > 1 {
+            return self.check(&Type::Object(object), sup);
+          }
+        }
+        types.iter().any(|t| self.check(t, sup))
+      }
+
+      // boolean is true | false
+      (Type::Boolean, Type::Or(_)) => {
+        self.check(&Type::Literal(Literal::Boolean(true)), sup) && self.check(&Type::Literal(Literal::Boolean(false)), sup)
+      }
+      (_, Type::Or(types)) => types.iter().any(|t| self.check(sub, t)),
+
+      (Type::Null, Type::Null)
+      | (Type::Undefined, Type::Undefined)
+      | (Type::Boolean, Type::Boolean)
+      | (Type::Number, Type::Number)
+      | (Type::String, Type::String) => true,
+
+      (Type::Literal(a), Type::Literal(b)) => a == b,
+      (Type::Literal(Literal::String(_)), Type::String)
+      | (Type::Literal(Literal::Number(_)), Type::Number)
+      | (Type::Literal(Literal::Boolean(_)), Type::Boolean) => true,
+
+      (Type::Custom(a), Type::Custom(b)) => a == b,
+
+      (Type::Tuple(a), Type::Tuple(b)) => self.tuple(a, b),
+      (Type::Tuple(a), Type::Array(b)) => {
+        a.items.iter().all(|t| self.check(t, &b.item)) && a.rest.as_ref().map(|rest| self.check(rest, &b.item)).unwrap_or(true)
+      }
+      (Type::Array(a), Type::Array(b)) => self.check(&a.item, &b.item),
+      (Type::Array(a), Type::Tuple(b)) => self.array_tuple(a, b),
+
+      (Type::Object(a), Type::Object(b)) => self.object(a, b),
+      (Type::Record(a), Type::Object(b)) => self.record_object(a, b),
+      (Type::Object(a), Type::Record(b)) => self.object_record(a, b),
+      (Type::Record(a), Type::Record(b)) => self.record(a, b),
+
+      _ => false,
+    }
+  }
+
+  fn resolve(&self, name: &str) -> Option<Type> {
+    self.definitions?.get(name).cloned()
+  }
+
+  fn resolve_other(&self, name: &str) -> Option<Type> {
+    self.other_definitions?.get(name).cloned()
+  }
+
+  fn instantiate(&self, name: &str, args: &[Type]) -> Option<Type> {
+    self.definitions?.instantiate(name, args)
+  }
+
+  fn instantiate_other(&self, name: &str, args: &[Type]) -> Option<Type> {
+    self.other_definitions?.instantiate(name, args)
+  }
+
+  /// The type of the `i`th element of a tuple, None if there can't be an element at that position
+  fn element(tuple: &Tuple, i: usize) -> Option<Type> {
+    match tuple.items.get(i) {
+      Some(item) if i >= tuple.required() => Some(Type::Or(vec![item.clone(), Type::Undefined])),
+      Some(item) => Some(item.clone()),
+      None => tuple.rest.as_ref().map(|rest| Type::Or(vec![(**rest).clone(), Type::Undefined])),
+    }
+  }
+
+  fn tuple(&mut self, a: &Tuple, b: &Tuple) -> bool {
+    let len = a.items.len().max(b.items.len());
+    for i in 0..len {
+      let ok = match (Self::element(a, i), Self::element(b, i)) {
+        (Some(sub), Some(sup)) => self.check(&sub, &sup),
+        // a ended, b must accept the element to be absent
+        (None, Some(sup)) => accepts_undefined(&sup),
+        // b ended, the element must be always absent in a
+        (Some(sub), None) => self.check(&sub, &Type::Undefined),
+        (None, None) => true,
+      };
+      if !ok {
+        return false;
+      }
+    }
+
+    match (&a.rest, &b.rest) {
+      (None, _) => true,
+      (Some(rest), None) => matches!(**rest, Type::Never),
+      (Some(a), Some(b)) => self.check(a, b),
+    }
+  }
+
+  fn array_tuple(&mut self, a: &Array, b: &Tuple) -> bool {
+    let item = Type::Or(vec![(*a.item).clone(), Type::Undefined]);
+    b.items.iter().all(|t| self.check(&item, t)) && b.rest.as_ref().map(|rest| self.check(&a.item, rest)).unwrap_or(false)
+  }
+
+  fn object(&mut self, a: &Object, b: &Object) -> bool {
+    // an open object may have any other key
+    if b.exact && (!a.exact || a.properties.keys().any(|key| !b.properties.contains_key(key))) {
+      return false;
+    }
+
+    b.properties.iter().all(|(key, sup)| {
+      let sup_optional = sup.optional || accepts_undefined(&sup.ty);
+      match a.properties.get(key) {
+        None => sup_optional,
+        Some(sub) => {
+          if sub.optional && !sup_optional {
+            return false;
+          }
+          self.check(&sub.ty, &sup.ty)
+        }
+      }
+    })
+  }
+
+  fn record_object(&mut self, a: &Record, b: &Object) -> bool {
+    if b.exact {
+      let mut keys = vec![];
+      literal_keys(&a.key, &mut keys);
+      if !is_finite_key(&a.key) || keys.iter().any(|key| !b.properties.contains_key(key)) {
+        return false;
+      }
+    }
+
+    b.properties.iter().all(|(key, sup)| {
+      let sup_optional = sup.optional || accepts_undefined(&sup.ty);
+      if !key_matches(&a.key, key, self.definitions) {
+        return sup_optional;
+      }
+      if !sup_optional && (a.optional || !is_finite_key(&a.key)) {
+        return false;
+      }
+      self.check(&a.value, &sup.ty)
+    })
+  }
+
+  fn object_record(&mut self, a: &Object, b: &Record) -> bool {
+    let props_ok = a
+      .properties
+      .iter()
+      .all(|(key, sub)| !is_unchecked_key(&b.key) && key_matches(&b.key, key, self.other_definitions) && self.check(&sub.ty, &b.value));
+
+    if !props_ok {
+      return false;
+    }
+
+    if b.optional {
+      return true;
+    }
+
+    // every literal key of a total record must be present
+    let mut keys = vec![];
+    literal_keys(&b.key, &mut keys);
+    keys.iter().all(|key| match a.properties.get(key) {
+      Some(prop) => !prop.optional || accepts_undefined(&b.value),
+      None => accepts_undefined(&b.value),
+    })
+  }
+
+  fn record(&mut self, a: &Record, b: &Record) -> bool {
+    // keys are always strings in JSON
+    let keys = matches!(*b.key, Type::String) || self.check(&a.key, &b.key);
+    if !keys || !self.check(&a.value, &b.value) {
+      return false;
+    }
+    if b.optional || !is_finite_key(&b.key) {
+      return true;
+    }
+    !a.optional && self.check(&b.key, &a.key)
+  }
+}
+
+fn is_never(ty: &Type) -> bool {
+  match ty {
+    Type::Never => true,
+    Type::And(types) => types.is_empty(),
+    _ => false,
+  }
+}
+
+/// Merges an intersection of objects into a single object, returns None if some member is not an object or a key is repeated.
+/// The result is exact if any member is
+pub(crate) fn merge_objects(types: &[Type]) -> Option<Object> {
+  let mut object = Object { properties: Default::default(), exact: false };
+  for ty in types {
+    let other = match ty {
+      Type::Object(other) => other.clone(),
+      Type::And(types) => merge_objects(types)?,
+      _ => return None,
+    };
+    object.exact |= other.exact;
+    for (key, prop) in other.properties {
+      if object.properties.contains_key(&key) {
+        return None;
+      }
+      object.properties.insert(key, prop);
+    }
+  }
+  Some(object)
+}
+
+/// Custom and parameter keys accept any key, the check can't prove a property is one of them
+fn is_unchecked_key(ty: &Type) -> bool {
+  match ty {
+    Type::Custom(_) | Type::Param(_) => true,
+    Type::Or(types) | Type::And(types) => types.iter().any(is_unchecked_key),
+    _ => false,
+  }
+}
+
+/// A key type made only of literals
+fn is_finite_key(ty: &Type) -> bool {
+  match ty {
+    Type::Literal(_) => true,
+    Type::Or(types) => types.iter().all(is_finite_key),
+    _ => false,
+  }
+}
+
+fn literal_keys(ty: &Type, keys: &mut Vec<String>) {
+  match ty {
+    Type::Literal(Literal::String(value)) => keys.push(value.clone()),
+    Type::Literal(Literal::Number(value)) => keys.push(value.to_string()),
+    Type::Literal(Literal::Boolean(value)) => keys.push(value.to_string()),
+    Type::Or(types) => types.iter().for_each(|t| literal_keys(t, keys)),
+    _ => {}
+  }
+}
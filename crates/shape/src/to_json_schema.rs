@@ -0,0 +1,240 @@
+use serde_json::{json, Map, Value};
+
+use crate::{definitions::identifier, Array, Definitions, Literal, Object, Record, Shape, ShapeOptions, Tuple, Type};
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Converts a type to a JSON Schema (2020-12)
+///
+/// [Type::Ref] is converted to a `$ref` to `#/$defs/{name}`, see [json_schema] to build a full document
+pub trait ToJsonSchema {
+  fn to_json_schema(&self) -> Value;
+}
+
+/// Builds a JSON Schema document for `T` with every named type it uses in `$defs`
+///
+/// Serialize and deserialize shapes are different, call this once with each [ShapeOptions]
+pub fn json_schema<T: Shape + ?Sized>(options: &ShapeOptions) -> Value {
+  let mut definitions = Definitions::new();
+  let ty = definitions.add::<T>(options);
+  json_schema_document(&ty, &definitions)
+}
+
+/// Builds a JSON Schema document for `ty` with `definitions` in `$defs`,
+/// generic instances are added to `$defs` as separate definitions, see [Definitions::monomorphize]
+pub fn json_schema_document(ty: &Type, definitions: &Definitions) -> Value {
+  let (ty, definitions) = definitions.monomorphize(ty);
+  let (ty, definitions) = (&ty, &definitions);
+  let mut document = Map::new();
+  document.insert("$schema".into(), json!(JSON_SCHEMA_DIALECT));
+
+  match definitions.resolve_keys(ty).to_json_schema() {
+    Value::Object(map) => document.extend(map),
+    // boolean schemas can't be merged with other keywords
+    schema => {
+      document.insert("allOf".into(), json!([schema]));
+    }
+  }
+
+  if !definitions.is_empty() {
+    document.insert("$defs".into(), defs(definitions));
+  }
+
+  Value::Object(document)
+}
+
+/// Builds a JSON Schema document with only `definitions` in `$defs`, for a set of types without a root
+pub fn json_schema_definitions(definitions: &Definitions) -> Value {
+  let (_, definitions) = definitions.monomorphize(&Type::Never);
+  json!({
+    "$schema": JSON_SCHEMA_DIALECT,
+    "$defs": defs(&definitions),
+  })
+}
+
+fn defs(definitions: &Definitions) -> Value {
+  Value::Object(
+    definitions
+      .iter()
+      .map(|(name, ty)| (identifier(name), described(definitions.resolve_keys(ty).to_json_schema(), definitions.description(name))))
+      .collect::<Map<String, Value>>(),
+  )
+}
+
+/// Adds the description of a definition to its schema, boolean schemas can't have one
+pub(crate) fn described(mut schema: Value, description: Option<&str>) -> Value {
+  if let (Value::Object(map), Some(description)) = (&mut schema, description) {
+    map.insert("description".into(), json!(description));
+  }
+  schema
+}
+
+/// JSON has no undefined, a type that accepts undefined means the value can be absent
+pub(crate) fn accepts_undefined(ty: &Type) -> bool {
+  match ty {
+    Type::Undefined => true,
+    Type::Or(types) => types.iter().any(accepts_undefined),
+    _ => false,
+  }
+}
+
+/// Removes undefined from unions, the absence of the value is handled by the container
+pub(crate) fn without_undefined(ty: &Type) -> Type {
+  match ty {
+    Type::Or(types) => Type::Or(
+      types
+        .iter()
+        .filter(|t| !matches!(t, Type::Undefined))
+        .map(without_undefined)
+        .collect(),
+    ),
+    ty => ty.clone(),
+  }
+}
+
+impl ToJsonSchema for Literal {
+  fn to_json_schema(&self) -> Value {
+    match self {
+      Literal::String(value) => json!({ "const": value }),
+      Literal::Number(value) => json!({ "const": value }),
+      Literal::Boolean(value) => json!({ "const": value }),
+    }
+  }
+}
+
+impl ToJsonSchema for Tuple {
+  fn to_json_schema(&self) -> Value {
+    let mut schema = Map::new();
+    schema.insert("type".into(), json!("array"));
+    if !self.items.is_empty() {
+      schema.insert(
+        "prefixItems".into(),
+        Value::Array(self.items.iter().map(|t| t.to_json_schema()).collect()),
+      );
+    }
+    schema.insert("minItems".into(), json!(self.required()));
+    match &self.rest {
+      None => {
+        schema.insert("items".into(), json!(false));
+      }
+      Some(rest) => {
+        schema.insert("items".into(), rest.to_json_schema());
+      }
+    }
+    Value::Object(schema)
+  }
+}
+
+impl ToJsonSchema for Array {
+  fn to_json_schema(&self) -> Value {
+    json!({
+      "type": "array",
+      "items": self.item.to_json_schema(),
+    })
+  }
+}
+
+impl ToJsonSchema for Object {
+  fn to_json_schema(&self) -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for (key, prop) in self.properties.iter() {
+      let optional = prop.optional || accepts_undefined(&prop.ty);
+      let mut schema = without_undefined(&prop.ty).to_json_schema();
+      if let Value::Object(map) = &mut schema {
+        if prop.readonly {
+          map.insert("readOnly".into(), json!(true));
+        }
+        if let Some(description) = &prop.description {
+          map.insert("description".into(), json!(description));
+        }
+      }
+      properties.insert(key.clone(), schema);
+      if !optional {
+        required.push(json!(key));
+      }
+    }
+
+    let mut schema = json!({
+      "type": "object",
+      "properties": properties,
+      "required": required,
+    });
+
+    if self.exact {
+      schema["additionalProperties"] = json!(false);
+    }
+
+    schema
+  }
+}
+
+/// Schema for the keys of a record, keys are always strings in JSON
+fn key_schema(key: &Type) -> Value {
+  match key {
+    Type::String => json!({ "type": "string" }),
+    Type::Number => json!({ "type": "string", "pattern": r"^-?\d+(\.\d+)?([eE][+-]?\d+)?$" }),
+    Type::Literal(Literal::String(value)) => json!({ "const": value }),
+    Type::Literal(Literal::Number(value)) => json!({ "const": value.to_string() }),
+    Type::Literal(Literal::Boolean(value)) => json!({ "const": value.to_string() }),
+    Type::Boolean => json!({ "enum": ["true", "false"] }),
+    Type::Or(types) => json!({ "anyOf": types.iter().map(key_schema).collect::<Vec<_>>() }),
+    Type::And(types) => json!({ "allOf": types.iter().map(key_schema).collect::<Vec<_>>() }),
+    Type::Custom(_) | Type::Param(_) => key.to_json_schema(),
+    // the schema of a named type is for values, any string can be a key of it, see [Definitions::resolve_keys]
+    Type::Ref(_) | Type::Generic(_) => json!({ "type": "string" }),
+    Type::Null | Type::Undefined | Type::Never | Type::Tuple(_) | Type::Array(_) | Type::Object(_) | Type::Record(_) => {
+      json!(false)
+    }
+  }
+}
+
+impl ToJsonSchema for Record {
+  fn to_json_schema(&self) -> Value {
+    let mut schema = json!({
+      "type": "object",
+      "additionalProperties": self.value.to_json_schema(),
+    });
+    if !matches!(*self.key, Type::String) {
+      schema["propertyNames"] = key_schema(&self.key);
+    }
+    schema
+  }
+}
+
+impl ToJsonSchema for Type {
+  fn to_json_schema(&self) -> Value {
+    match self {
+      Type::String => json!({ "type": "string" }),
+      Type::Number => json!({ "type": "number" }),
+      Type::Boolean => json!({ "type": "boolean" }),
+      Type::Null => json!({ "type": "null" }),
+      // a JSON value can't be undefined
+      Type::Undefined => json!(false),
+      Type::Never => json!(false),
+      Type::Literal(literal) => literal.to_json_schema(),
+      Type::Tuple(tuple) => tuple.to_json_schema(),
+      Type::Array(array) => array.to_json_schema(),
+      Type::Object(object) => object.to_json_schema(),
+      Type::Record(record) => record.to_json_schema(),
+      Type::And(types) => {
+        if types.is_empty() {
+          return json!(false);
+        }
+        json!({ "allOf": types.iter().map(|t| t.to_json_schema()).collect::<Vec<_>>() })
+      }
+      Type::Or(types) => {
+        if types.is_empty() {
+          return json!(false);
+        }
+        json!({ "anyOf": types.iter().map(|t| t.to_json_schema()).collect::<Vec<_>>() })
+      }
+      // custom types can't be described in JSON Schema, accept anything
+      Type::Custom(_) => json!({}),
+      Type::Ref(name) => json!({ "$ref": format!("#/$defs/{}", identifier(name)) }),
+      // JSON Schema has no generics, documents replace these with their instances, see [Definitions::monomorphize]
+      Type::Generic(generic) => json!({ "$ref": format!("#/$defs/{}", identifier(&generic.name)) }),
+      Type::Param(_) => json!({}),
+    }
+  }
+}
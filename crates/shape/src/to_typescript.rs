@@ -1,4 +1,4 @@
-use crate::{Array, Literal, Object, Record, Tuple, Type};
+use crate::{definitions::identifier, Array, Literal, Object, Record, Tuple, Type};
 
 pub trait ToTypescript {
   fn to_typescript(&self) -> String;
@@ -14,33 +14,12 @@ impl ToTypescript for Object {
   fn to_typescript(&self) -> String {
     let mut properties = vec![];
     for (key, prop) in self.properties.iter() {
-      
-      macro_rules! quote {
-        ($key:expr) => {
-          serde_json::to_string($key).unwrap()
-        };
-      }
-
-      let quoted_key = {
-        let first = key.chars().nth(0);
-        match first {
-          None => String::from("\"\""),
-          Some(first) => {
-            if
-              !matches!(first, 'a'..='z' | 'A'..='Z' | '_') ||
-              key.contains(|c| !matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_')) 
-            {
-               quote!(key)
-            } else {
-              String::from(key)
-            }
-          }
-        }
-      };
+      let quoted_key = property_key(key);
 
       properties.push(
         format!(
-          "{readonly}{key}{optional}: {value};",
+          "{doc}{readonly}{key}{optional}: {value};",
+          doc = prop.description.as_deref().map(|doc| format!("{} ", doc_comment(doc))).unwrap_or_default(),
           readonly = if prop.readonly { "readonly " } else { "" },
           key = quoted_key,
           optional = if prop.optional { "?" } else { "" },
@@ -52,12 +31,39 @@ impl ToTypescript for Object {
   }
 }
 
+/// Renders a description as a TSDoc comment, Eg: `/** The id of the user */`
+pub(crate) fn doc_comment(doc: &str) -> String {
+  let doc = doc.replace("*/", "*\\/");
+  if doc.contains('\n') {
+    let lines = doc.lines().map(|line| if line.is_empty() { String::from(" *") } else { format!(" * {}", line) }).collect::<Vec<String>>();
+    format!("/**\n{}\n */", lines.join("\n"))
+  } else {
+    format!("/** {} */", doc)
+  }
+}
+
+/// Object keys are always strings, numbers are written as template literals
+fn key_typescript(key: &Type) -> String {
+  match key {
+    Type::Number => String::from("`${number}`"),
+    Type::Boolean => String::from("(\"true\" | \"false\")"),
+    Type::Literal(Literal::Number(value)) => serde_json::to_string(&value.to_string()).unwrap(),
+    Type::Literal(Literal::Boolean(value)) => serde_json::to_string(&value.to_string()).unwrap(),
+    Type::Or(types) if !types.is_empty() => {
+      format!("({})", types.iter().map(key_typescript).collect::<Vec<String>>().join(" | "))
+    }
+    key => key.to_typescript(),
+  }
+}
+
 impl ToTypescript for Record {
   fn to_typescript(&self) -> String {
-    if self.optional {
-      format!("Partial<Record<string, {}>>", self.value.to_typescript())
-    } else {
-      format!("Record<string, {}>", self.value.to_typescript())
+    let readonly = if self.readonly { "readonly " } else { "" };
+    match &*self.key {
+      // every string is a key, so there is nothing to make partial
+      Type::String => format!("{{ {}[key: string]: {} }}", readonly, self.value.to_typescript()),
+      key if self.optional => format!("Partial<Record<{}, {}>>", key_typescript(key), self.value.to_typescript()),
+      key => format!("Record<{}, {}>", key_typescript(key), self.value.to_typescript()),
     }
   }
 }
@@ -74,8 +80,17 @@ impl ToTypescript for Literal {
 
 impl ToTypescript for Tuple {
   fn to_typescript(&self) -> String {
-    let inner = self.items.iter().map(|t| t.to_typescript()).collect::<Vec<String>>().join(", ");
-    format!("[{}]", inner)
+    let required = self.required();
+    let mut items = self
+      .items
+      .iter()
+      .enumerate()
+      .map(|(i, t)| if i < required { t.to_typescript() } else { format!("{}?", t.to_typescript()) })
+      .collect::<Vec<String>>();
+    if let Some(rest) = &self.rest {
+      items.push(format!("...{}[]", rest.to_typescript()));
+    }
+    format!("[{}]", items.join(", "))
   }
 }
 
@@ -108,6 +123,31 @@ impl ToTypescript for Type {
         format!("({})", inner)
       }
       Type::Custom(custom) => custom.clone(),
+      Type::Ref(name) => identifier(name),
+      Type::Param(name) => name.clone(),
+      Type::Generic(generic) => format!(
+        "{}<{}>",
+        identifier(&generic.name),
+        generic.args.iter().map(|t| t.to_typescript()).collect::<Vec<String>>().join(", ")
+      ),
+    }
+  }
+}
+
+/// Quotes an object key if it's not a valid identifier
+pub(crate) fn property_key(key: &str) -> String {
+  let first = key.chars().next();
+  match first {
+    None => String::from("\"\""),
+    Some(first) => {
+      if
+        !matches!(first, 'a'..='z' | 'A'..='Z' | '_') ||
+        key.contains(|c| !matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_'))
+      {
+        serde_json::to_string(key).unwrap()
+      } else {
+        String::from(key)
+      }
     }
   }
-}
\ No newline at end of file
+}
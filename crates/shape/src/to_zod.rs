@@ -0,0 +1,215 @@
+use crate::{
+  definitions::identifier, subtype::merge_objects, to_typescript::property_key, Array, Literal, Object, Property, Record,
+  ToTypescript, Tuple, Type,
+};
+
+/// Converts a type to the source of a [zod](https://zod.dev) schema
+///
+/// Assumes `z` is in scope (`import { z } from "zod"`), [Type::Ref] is converted to a lazy reference to a schema with the same name
+pub trait ToZod {
+  fn to_zod(&self) -> String;
+}
+
+impl ToZod for Array {
+  fn to_zod(&self) -> String {
+    format!("z.array({})", self.item.to_zod())
+  }
+}
+
+impl ToZod for Object {
+  fn to_zod(&self) -> String {
+    let mut properties = vec![];
+    for (key, prop) in self.properties.iter() {
+      let mut value = prop.ty.to_zod();
+      if prop.optional && !value.ends_with(".optional()") {
+        value.push_str(".optional()");
+      }
+      if prop.readonly {
+        value.push_str(".readonly()");
+      }
+      if let Some(description) = &prop.description {
+        value.push_str(&describe(description));
+      }
+      properties.push(format!("{}: {}", property_key(key), value));
+    }
+
+    let mut out = if properties.is_empty() {
+      String::from("z.object({})")
+    } else {
+      format!("z.object({{ {} }})", properties.join(", "))
+    };
+
+    if self.exact {
+      out.push_str(".strict()");
+    }
+
+    out
+  }
+}
+
+/// Schema for the keys of a record, keys are always strings in JSON
+fn key_zod(key: &Type) -> String {
+  match key {
+    Type::Number => String::from(r"z.string().regex(/^-?\d+(\.\d+)?([eE][+-]?\d+)?$/)"),
+    Type::Literal(Literal::Number(value)) => format!("z.literal({})", serde_json::to_string(&value.to_string()).unwrap()),
+    Type::Literal(Literal::Boolean(value)) => format!("z.literal(\"{}\")", value),
+    Type::Boolean => String::from("z.enum([\"true\", \"false\"])"),
+    Type::Or(types) => union(types.iter().map(key_zod).collect()),
+    // the schema of a named type is for values, any string can be a key of it
+    Type::Ref(_) | Type::Generic(_) => String::from("z.string()"),
+    _ => key.to_zod(),
+  }
+}
+
+impl ToZod for Record {
+  fn to_zod(&self) -> String {
+    format!("z.record({}, {})", key_zod(&self.key), self.value.to_zod())
+  }
+}
+
+impl ToZod for Literal {
+  fn to_zod(&self) -> String {
+    format!("z.literal({})", self.to_typescript())
+  }
+}
+
+impl ToZod for Tuple {
+  fn to_zod(&self) -> String {
+    let required = self.required();
+    let inner = self
+      .items
+      .iter()
+      .enumerate()
+      .map(|(i, t)| if i < required { t.to_zod() } else { format!("{}.optional()", t.to_zod()) })
+      .collect::<Vec<String>>()
+      .join(", ");
+    match &self.rest {
+      None => format!("z.tuple([{}])", inner),
+      Some(rest) => format!("z.tuple([{}]).rest({})", inner, rest.to_zod()),
+    }
+  }
+}
+
+/// Attaches a description to a schema, Eg: `.describe("The id of the user")`
+pub(crate) fn describe(description: &str) -> String {
+  format!(".describe({})", serde_json::to_string(description).unwrap())
+}
+
+fn union(mut members: Vec<String>) -> String {
+  match members.len() {
+    0 => String::from("z.never()"),
+    1 => members.pop().unwrap(),
+    _ => format!("z.union([{}])", members.join(", ")),
+  }
+}
+
+/// Returns the object form of the members of a union, if all of them are objects
+fn union_objects(types: &[Type]) -> Option<Vec<Object>> {
+  types
+    .iter()
+    .map(|ty| match ty {
+      Type::Object(object) => Some(object.clone()),
+      Type::And(types) => merge_objects(types),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Finds a property present in every object with a distinct string literal type in each one
+fn discriminator(objects: &[Object]) -> Option<&str> {
+  let first = objects.first()?;
+  first.properties.keys().map(String::as_str).find(|key| {
+    let mut seen = vec![];
+    objects.iter().all(|object| match object.properties.get(*key) {
+      Some(Property { optional: false, ty: Type::Literal(Literal::String(value)), .. }) => {
+        if seen.contains(&value) {
+          false
+        } else {
+          seen.push(value);
+          true
+        }
+      }
+      _ => false,
+    })
+  })
+}
+
+impl ToZod for Type {
+  fn to_zod(&self) -> String {
+    match self {
+      Type::String => String::from("z.string()"),
+      Type::Number => String::from("z.number()"),
+      Type::Boolean => String::from("z.boolean()"),
+      Type::Null => String::from("z.null()"),
+      Type::Undefined => String::from("z.undefined()"),
+      Type::Never => String::from("z.never()"),
+      Type::Literal(literal) => literal.to_zod(),
+      Type::Tuple(tuple) => tuple.to_zod(),
+      Type::Array(array) => array.to_zod(),
+      Type::Object(object) => object.to_zod(),
+      Type::Record(record) => record.to_zod(),
+      Type::And(types) => {
+        if types.is_empty() {
+          return String::from("z.never()");
+        }
+        if let Some(object) = merge_objects(types) {
+          return object.to_zod();
+        }
+        let mut iter = types.iter();
+        let first = iter.next().unwrap().to_zod();
+        iter.fold(first, |acc, t| format!("z.intersection({}, {})", acc, t.to_zod()))
+      }
+      Type::Or(types) => {
+        // Option<T> like unions are rendered as T.nullable().optional()
+        let null = types.iter().any(|t| matches!(t, Type::Null));
+        let undefined = types.iter().any(|t| matches!(t, Type::Undefined));
+        let rest = types.iter().filter(|t| !matches!(t, Type::Null | Type::Undefined)).cloned().collect::<Vec<Type>>();
+
+        let mut out = if rest.is_empty() {
+          if null {
+            return if undefined { String::from("z.null().optional()") } else { String::from("z.null()") };
+          }
+          if undefined {
+            return String::from("z.undefined()");
+          }
+          return String::from("z.never()");
+        } else if rest.len() > 1 {
+          match union_objects(&rest) {
+            Some(objects) => match discriminator(&objects) {
+              Some(key) => format!(
+                "z.discriminatedUnion({}, [{}])",
+                serde_json::to_string(key).unwrap(),
+                objects.iter().map(|o| o.to_zod()).collect::<Vec<String>>().join(", ")
+              ),
+              None => union(rest.iter().map(|t| t.to_zod()).collect()),
+            },
+            None => union(rest.iter().map(|t| t.to_zod()).collect()),
+          }
+        } else {
+          rest[0].to_zod()
+        };
+
+        if null {
+          out.push_str(".nullable()");
+        }
+        if undefined {
+          out.push_str(".optional()");
+        }
+        out
+      }
+      Type::Custom(custom) => match custom.as_str() {
+        "any" => String::from("z.any()"),
+        "unknown" => String::from("z.unknown()"),
+        custom => format!("z.custom<{}>()", custom),
+      },
+      Type::Ref(name) => format!("z.lazy(() => {})", identifier(name)),
+      // generic schemas are functions of the schemas of their parameters
+      Type::Param(name) => name.clone(),
+      Type::Generic(generic) => format!(
+        "z.lazy(() => {}({}))",
+        identifier(&generic.name),
+        generic.args.iter().map(|t| t.to_zod()).collect::<Vec<String>>().join(", ")
+      ),
+    }
+  }
+}
@@ -0,0 +1,336 @@
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::{to_json_schema::accepts_undefined, Array, Definitions, Literal, Object, Record, ToTypescript, Tuple, Type};
+
+/// Checks a JSON value against a type and reports every mismatch
+///
+/// This is the detailed version of [crate::IsAsignable]
+pub trait Validate {
+  fn validate(&self, v: &Value) -> Result<(), Vec<ValidationError>>;
+
+  /// Like [Validate::validate] but [Type::Ref]s are resolved with `definitions`
+  fn validate_with(&self, v: &Value, definitions: &Definitions) -> Result<(), Vec<ValidationError>>;
+}
+
+/// A value that doesn't match its expected type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+  /// JSON pointer to the value, Eg: `/users/0/name`
+  pub path: String,
+  pub expected: Type,
+  /// A short description of the value found, Eg: `number 1`, `array` or `missing`
+  pub found: String,
+  /// For unions, the index of the closest matching variant and its errors
+  pub closest: Option<(usize, Vec<ValidationError>)>,
+}
+
+impl fmt::Display for ValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let path = if self.path.is_empty() { "/" } else { &self.path };
+    write!(f, "{}: expected {}, found {}", path, self.expected.to_typescript(), self.found)?;
+    if let Some((index, errors)) = &self.closest {
+      write!(f, " (closest variant is #{}: ", index)?;
+      for (i, error) in errors.iter().enumerate() {
+        if i != 0 {
+          write!(f, ", ")?;
+        }
+        write!(f, "{}", error)?;
+      }
+      write!(f, ")")?;
+    }
+    Ok(())
+  }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Validate for Type {
+  fn validate(&self, v: &Value) -> Result<(), Vec<ValidationError>> {
+    Validator { definitions: None }.run(self, v)
+  }
+
+  fn validate_with(&self, v: &Value, definitions: &Definitions) -> Result<(), Vec<ValidationError>> {
+    Validator { definitions: Some(definitions) }.run(self, v)
+  }
+}
+
+fn describe(v: &Value) -> String {
+  match v {
+    Value::Null => String::from("null"),
+    Value::Bool(v) => format!("boolean {}", v),
+    Value::Number(v) => format!("number {}", v),
+    Value::String(v) => {
+      if v.chars().count() > 32 {
+        let short = v.chars().take(32).collect::<String>();
+        format!("string {}...", serde_json::to_string(&short).unwrap())
+      } else {
+        format!("string {}", serde_json::to_string(v).unwrap())
+      }
+    }
+    Value::Array(_) => String::from("array"),
+    Value::Object(_) => String::from("object"),
+  }
+}
+
+const MISSING: &str = "missing";
+
+fn pointer(path: &str, key: &str) -> String {
+  format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+struct Validator<'a> {
+  definitions: Option<&'a Definitions>,
+}
+
+impl Validator<'_> {
+  fn run(&self, ty: &Type, v: &Value) -> Result<(), Vec<ValidationError>> {
+    let mut errors = vec![];
+    self.validate(ty, v, "", &mut errors);
+    if errors.is_empty() {
+      Ok(())
+    } else {
+      Err(errors)
+    }
+  }
+
+  fn error(&self, errors: &mut Vec<ValidationError>, path: &str, expected: &Type, found: String) {
+    errors.push(ValidationError {
+      path: path.to_string(),
+      expected: expected.clone(),
+      found,
+      closest: None,
+    })
+  }
+
+  fn validate(&self, ty: &Type, v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let ok = match ty {
+      Type::String => v.is_string(),
+      Type::Number => v.is_number(),
+      Type::Boolean => v.is_boolean(),
+      Type::Null => v.is_null(),
+      Type::Undefined => false,
+      Type::Never => false,
+      // custom types can't be checked
+      Type::Custom(_) => true,
+      Type::Literal(literal) => literal_matches(literal, v),
+      Type::Tuple(tuple) => return self.tuple(ty, tuple, v, path, errors),
+      Type::Array(array) => return self.array(ty, array, v, path, errors),
+      Type::Object(object) => return self.object(ty, object, v, path, errors),
+      Type::Record(record) => return self.record(ty, record, v, path, errors),
+      Type::And(types) if !types.is_empty() => {
+        for t in types {
+          self.validate(t, v, path, errors);
+        }
+        return;
+      }
+      Type::And(_) => false,
+      Type::Or(types) => return self.or(ty, types, v, path, errors),
+      Type::Ref(name) => match self.definitions.and_then(|definitions| definitions.get(name)) {
+        Some(resolved) => return self.validate(resolved, v, path, errors),
+        None => {
+          self.error(errors, path, ty, format!("{} (unresolved reference)", describe(v)));
+          return;
+        }
+      },
+      Type::Generic(generic) => match self.definitions.and_then(|definitions| definitions.instantiate(&generic.name, &generic.args)) {
+        Some(resolved) => return self.validate(&resolved, v, path, errors),
+        None => {
+          self.error(errors, path, ty, format!("{} (unresolved reference)", describe(v)));
+          return;
+        }
+      },
+      // a parameter of a template, any value can be an instance of it
+      Type::Param(_) => true,
+    };
+
+    if !ok {
+      self.error(errors, path, ty, describe(v));
+    }
+  }
+
+  fn tuple(&self, ty: &Type, tuple: &Tuple, v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let items = match v {
+      Value::Array(items) => items,
+      _ => return self.error(errors, path, ty, describe(v)),
+    };
+
+    for (i, t) in tuple.items.iter().enumerate() {
+      match items.get(i) {
+        Some(item) => self.validate(t, item, &pointer(path, &i.to_string()), errors),
+        None => {
+          if i < tuple.required() && !accepts_undefined(t) {
+            self.error(errors, &pointer(path, &i.to_string()), t, String::from(MISSING));
+          }
+        }
+      }
+    }
+
+    for (i, item) in items.iter().enumerate().skip(tuple.items.len()) {
+      match &tuple.rest {
+        Some(rest) => self.validate(rest, item, &pointer(path, &i.to_string()), errors),
+        None => self.error(errors, &pointer(path, &i.to_string()), &Type::Never, describe(item)),
+      }
+    }
+  }
+
+  fn array(&self, ty: &Type, array: &Array, v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    match v {
+      Value::Array(items) => {
+        for (i, item) in items.iter().enumerate() {
+          self.validate(&array.item, item, &pointer(path, &i.to_string()), errors);
+        }
+      }
+      _ => self.error(errors, path, ty, describe(v)),
+    }
+  }
+
+  fn object(&self, ty: &Type, object: &Object, v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let map = match v {
+      Value::Object(map) => map,
+      _ => return self.error(errors, path, ty, describe(v)),
+    };
+
+    for (key, prop) in object.properties.iter() {
+      match map.get(key) {
+        Some(v) => self.validate(&prop.ty, v, &pointer(path, key), errors),
+        None => {
+          if !prop.optional && !accepts_undefined(&prop.ty) {
+            self.error(errors, &pointer(path, key), &prop.ty, String::from(MISSING));
+          }
+        }
+      }
+    }
+
+    if object.exact {
+      for key in map.keys().filter(|key| !object.properties.contains_key(*key)) {
+        self.error(errors, &pointer(path, key), &Type::Never, format!("key {}", serde_json::to_string(key).unwrap()));
+      }
+    }
+  }
+
+  fn record(&self, ty: &Type, record: &Record, v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let map = match v {
+      Value::Object(map) => map,
+      _ => return self.error(errors, path, ty, describe(v)),
+    };
+
+    for (key, value) in map.iter() {
+      if !key_matches(&record.key, key, self.definitions) {
+        self.error(errors, &pointer(path, key), &record.key, format!("key {}", serde_json::to_string(key).unwrap()));
+      } else {
+        self.validate(&record.value, value, &pointer(path, key), errors);
+      }
+    }
+
+    if !record.optional {
+      self.required_keys(&record.key, &record.value, map, path, errors);
+    }
+  }
+
+  /// Literal keys of a non partial record must be present, a boolean key is `true` and `false`
+  fn required_keys(&self, key: &Type, value: &Type, map: &Map<String, Value>, path: &str, errors: &mut Vec<ValidationError>) {
+    match key {
+      Type::Literal(literal) => {
+        let key = literal_key(literal);
+        if !map.contains_key(&key) && !accepts_undefined(value) {
+          self.error(errors, &pointer(path, &key), value, String::from(MISSING));
+        }
+      }
+      Type::Boolean => {
+        for literal in [Literal::Boolean(true), Literal::Boolean(false)] {
+          self.required_keys(&Type::Literal(literal), value, map, path, errors);
+        }
+      }
+      Type::Or(types) => {
+        for t in types {
+          self.required_keys(t, value, map, path, errors);
+        }
+      }
+      Type::Ref(name) => {
+        if let Some(resolved) = self.definitions.and_then(|definitions| definitions.get(name)) {
+          self.required_keys(resolved, value, map, path, errors);
+        }
+      }
+      _ => {}
+    }
+  }
+
+  fn or(&self, ty: &Type, types: &[Type], v: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let mut closest: Option<(usize, Vec<ValidationError>)> = None;
+    for (i, t) in types.iter().enumerate() {
+      let mut variant_errors = vec![];
+      self.validate(t, v, path, &mut variant_errors);
+      if variant_errors.is_empty() {
+        return;
+      }
+
+      let is_closer = match &closest {
+        None => true,
+        Some((_, current)) => distance(&variant_errors, path) < distance(current, path),
+      };
+
+      if is_closer {
+        closest = Some((i, variant_errors));
+      }
+    }
+
+    errors.push(ValidationError {
+      path: path.to_string(),
+      expected: ty.clone(),
+      found: describe(v),
+      closest,
+    })
+  }
+}
+
+/// How far a union variant is from matching a value,
+/// a mismatch of the value itself is worse than a mismatched discriminator (literal) which is worse than any other nested error
+fn distance(errors: &[ValidationError], path: &str) -> (bool, usize, usize) {
+  let root = errors.iter().any(|e| e.path == path && e.closest.is_none());
+  let literals = errors.iter().filter(|e| matches!(e.expected, Type::Literal(_))).count();
+  (root, literals, errors.len())
+}
+
+fn literal_matches(literal: &Literal, v: &Value) -> bool {
+  match (literal, v) {
+    (Literal::String(l), Value::String(v)) => l == v,
+    (Literal::Number(l), Value::Number(v)) => v.as_f64() == Some(*l),
+    (Literal::Boolean(l), Value::Bool(v)) => l == v,
+    _ => false,
+  }
+}
+
+/// JSON object keys are always strings, number and boolean keys are matched by their string form.
+/// Shared by [Validate], [crate::IsAsignable] and the subtype check so a record accepts the same keys in each
+pub(crate) fn key_matches(ty: &Type, key: &str, definitions: Option<&Definitions>) -> bool {
+  match ty {
+    Type::String => true,
+    Type::Number => key.parse::<f64>().is_ok(),
+    Type::Boolean => key == "true" || key == "false",
+    Type::Literal(Literal::Number(value)) => key.parse::<f64>() == Ok(*value),
+    Type::Literal(literal) => literal_key(literal) == key,
+    Type::Or(types) => types.iter().any(|t| key_matches(t, key, definitions)),
+    Type::And(types) => !types.is_empty() && types.iter().all(|t| key_matches(t, key, definitions)),
+    // custom types and parameters can't be checked
+    Type::Custom(_) | Type::Param(_) => true,
+    Type::Ref(name) => match definitions.and_then(|definitions| definitions.get(name)) {
+      Some(resolved) => key_matches(resolved, key, definitions),
+      None => false,
+    },
+    Type::Generic(generic) => match definitions.and_then(|definitions| definitions.instantiate(&generic.name, &generic.args)) {
+      Some(resolved) => key_matches(&resolved, key, definitions),
+      None => false,
+    },
+    Type::Null | Type::Undefined | Type::Never | Type::Tuple(_) | Type::Array(_) | Type::Object(_) | Type::Record(_) => false,
+  }
+}
+
+pub(crate) fn literal_key(literal: &Literal) -> String {
+  match literal {
+    Literal::String(value) => value.clone(),
+    Literal::Number(value) => value.to_string(),
+    Literal::Boolean(value) => value.to_string(),
+  }
+}
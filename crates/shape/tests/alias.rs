@@ -0,0 +1,63 @@
+mod common;
+
+use shape::{Shape, ShapeOptions, ToTypescript};
+
+#[test]
+fn field_alias() {
+  #[allow(unused)]
+  #[derive(Shape, serde::Deserialize)]
+  struct User {
+    id: u64,
+    #[serde(rename = "fullName", alias = "name", alias = "full_name")]
+    full_name: String,
+  }
+
+  eq!(
+    User::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ id: number; fullName: string; }"
+  );
+
+  eq!(
+    User::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "({ id: number; } & ({ fullName: string; } | { name: string; } | { full_name: string; }))"
+  );
+}
+
+#[test]
+fn variant_alias() {
+  #[allow(unused)]
+  #[derive(Shape, serde::Deserialize)]
+  #[serde(tag = "type")]
+  enum Event {
+    #[serde(alias = "click")]
+    Click { x: i32 },
+    Key { code: String },
+  }
+
+  eq!(
+    Event::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "(({ type: \"Click\"; } & { x: number; }) | ({ type: \"Key\"; } & { code: string; }))"
+  );
+
+  eq!(
+    Event::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "(({ type: \"Click\"; } & { x: number; }) | ({ type: \"click\"; } & { x: number; }) | ({ type: \"Key\"; } & { code: string; }))"
+  );
+}
+
+#[test]
+fn unit_variant_alias() {
+  #[allow(unused)]
+  #[derive(Shape, serde::Deserialize)]
+  enum Role {
+    #[serde(alias = "admin", alias = "ADMIN")]
+    Admin,
+    Guest,
+  }
+
+  eq!(Role::shape(&ShapeOptions::for_serialize()).to_typescript(), "(\"Admin\" | \"Guest\")");
+  eq!(
+    Role::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "(\"Admin\" | \"admin\" | \"ADMIN\" | \"Guest\")"
+  );
+}
@@ -38,6 +38,7 @@ fn static_array() {
     Tuple {
       items: vec![Type::Number, Type::Number, Type::Number],
       rest: None,
+      optional: 0,
     }
   );
 
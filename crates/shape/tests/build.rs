@@ -0,0 +1,109 @@
+mod common;
+
+use std::path::PathBuf;
+
+use shape::{
+  build::{write_if_changed, Backend, Bindings, Layout},
+  ShapeOptions, TypescriptExporter,
+};
+
+mod api {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    id: u64,
+    role: roles::Role,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[shape(generic)]
+  pub struct Page<T> {
+    items: Vec<T>,
+  }
+
+  pub mod roles {
+    use shape::Shape;
+
+    #[allow(unused)]
+    #[derive(Shape)]
+    pub enum Role {
+      Admin,
+      Guest,
+    }
+  }
+}
+
+fn exporter() -> TypescriptExporter {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<api::User>().add_as::<api::Page<api::User>>("Users");
+  exporter
+}
+
+fn files(bindings: Bindings) -> Vec<(String, String)> {
+  bindings.files().into_iter().map(|(path, contents)| (path.display().to_string(), contents)).collect()
+}
+
+#[test]
+fn per_module() {
+  eq!(
+    files(Bindings::new(exporter())),
+    vec![
+      (
+        "build/api.ts".to_string(),
+        "import type { Role } from \"./api/roles\";\n\
+         \n\
+         export type Page<T> = { items: Array<T>; };\n\
+         export type User = { id: number; role: Role; };\n"
+          .to_string()
+      ),
+      ("build/api/roles.ts".to_string(), "export type Role = (\"Admin\" | \"Guest\");\n".to_string()),
+      (
+        "index.ts".to_string(),
+        "import type { Page, User } from \"./build/api\";\n\
+         \n\
+         export type Users = Page<User>;\n"
+          .to_string()
+      ),
+    ]
+  );
+}
+
+#[test]
+fn per_type_zod() {
+  let files = files(Bindings::new(exporter()).layout(Layout::PerType).backend(Backend::Zod));
+  eq!(files.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>(), vec!["Page.ts", "Role.ts", "User.ts", "Users.ts"]);
+  eq!(
+    files[2].1,
+    "import { z } from \"zod\";\n\
+     import { Role } from \"./Role\";\n\
+     \n\
+     export type User = { id: number; role: Role; };\n\
+     export const User: z.ZodType<User> = z.object({ id: z.number(), role: z.lazy(() => Role) });\n"
+  );
+}
+
+#[test]
+fn single_file() {
+  let files = files(Bindings::new(exporter()).layout(Layout::Single("bindings".into())));
+  eq!(files.len(), 1);
+  eq!(files[0], ("bindings.ts".to_string(), exporter().export()));
+}
+
+#[test]
+fn write_only_changed_files() {
+  let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("build_bindings");
+  let _ = std::fs::remove_dir_all(&dir);
+
+  let bindings = Bindings::new(exporter());
+  eq!(bindings.write(&dir).unwrap().len(), 3);
+  eq!(std::fs::read_to_string(dir.join("build/api/roles.ts")).unwrap(), "export type Role = (\"Admin\" | \"Guest\");\n");
+  eq!(bindings.write(&dir).unwrap(), Vec::<PathBuf>::new());
+
+  std::fs::write(dir.join("index.ts"), "stale").unwrap();
+  eq!(bindings.write(&dir).unwrap(), vec![dir.join("index.ts")]);
+
+  assert!(!write_if_changed(dir.join("index.ts"), &bindings.files()[2].1).unwrap());
+}
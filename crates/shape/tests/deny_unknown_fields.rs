@@ -0,0 +1,135 @@
+mod common;
+
+use serde_json::json;
+use shape::{diff, IsAsignable, OpenApi, Shape, ShapeOptions, ShapeOptionsKind, ToJsonSchema, ToTypescript, ToZod, Type, Validate};
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(deny_unknown_fields)]
+struct Config {
+  port: u16,
+  host: Option<String>,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Open {
+  port: u16,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "type", deny_unknown_fields)]
+enum Internal {
+  A { a: u8 },
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(deny_unknown_fields)]
+struct Aliased {
+  #[serde(alias = "full_name")]
+  name: String,
+  #[serde(alias = "years")]
+  age: u8,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "t", content = "c", deny_unknown_fields)]
+enum Adjacent {
+  A { a: u8 },
+}
+
+fn exact(ty: &Type) -> Vec<bool> {
+  match ty {
+    Type::Object(object) => {
+      let mut out = vec![object.exact];
+      for prop in object.properties.values() {
+        out.extend(exact(&prop.ty));
+      }
+      out
+    }
+    Type::And(types) | Type::Or(types) => types.iter().flat_map(exact).collect(),
+    _ => vec![],
+  }
+}
+
+#[test]
+fn derive() {
+  eq!(exact(&Config::shape(&ShapeOptions::for_deserialize())), vec![true]);
+  eq!(exact(&Open::shape(&ShapeOptions::for_deserialize())), vec![false]);
+  // the tag is merged into the fields
+  eq!(exact(&Internal::shape(&ShapeOptions::for_deserialize())), vec![true]);
+  eq!(exact(&Adjacent::shape(&ShapeOptions::for_deserialize())), vec![true, true]);
+}
+
+#[test]
+fn validation() {
+  let ty = Config::shape(&ShapeOptions::for_deserialize());
+  assert!(ty.is_assignable(&json!({ "port": 80 })));
+  assert!(!ty.is_assignable(&json!({ "port": 80, "hots": "localhost" })));
+
+  let errors = ty.validate(&json!({ "port": 80, "hots": "localhost" })).unwrap_err();
+  eq!(
+    errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+    vec!["/hots: expected never, found key \"hots\"".to_string()]
+  );
+
+  assert!(Open::shape(&ShapeOptions::for_deserialize()).is_assignable(&json!({ "port": 80, "hots": "localhost" })));
+}
+
+#[test]
+fn internally_tagged_validation() {
+  let ty = Internal::shape(&ShapeOptions::for_deserialize());
+  eq!(ty.to_typescript(), "({ type: \"A\"; a: number; })");
+  assert!(ty.is_assignable(&json!({ "type": "A", "a": 1 })));
+  assert!(ty.validate(&json!({ "type": "A", "a": 1 })).is_ok());
+  assert!(!ty.is_assignable(&json!({ "type": "A", "a": 1, "typo": 2 })));
+  assert!(ty.validate(&json!({ "type": "A", "a": 1, "typo": 2 })).is_err());
+}
+
+#[test]
+fn aliased_validation() {
+  let ty = Aliased::shape(&ShapeOptions::for_deserialize());
+  for value in [json!({ "name": "a", "age": 1 }), json!({ "full_name": "a", "years": 1 })] {
+    assert!(ty.is_assignable(&value), "{}", value);
+    assert!(ty.validate(&value).is_ok(), "{}", value);
+  }
+  let typo = json!({ "name": "a", "age": 1, "typo": 1 });
+  assert!(!ty.is_assignable(&typo));
+  assert!(ty.validate(&typo).is_err());
+
+  // the serialize shape has no aliases
+  eq!(exact(&Aliased::shape(&ShapeOptions::for_serialize())), vec![true]);
+
+  let mut api = OpenApi::new("api", "1");
+  api.component::<Aliased>();
+  let document = api.document();
+  let input = &document["components"]["schemas"]["AliasedInput"];
+  eq!(input["anyOf"].as_array().unwrap().len(), 4);
+  for alternative in input["anyOf"].as_array().unwrap() {
+    eq!(alternative["additionalProperties"], json!(false));
+  }
+}
+
+#[test]
+fn backends() {
+  let ty = Config::shape(&ShapeOptions::for_serialize());
+  eq!(ty.to_json_schema()["additionalProperties"], json!(false));
+  eq!(ty.to_zod(), "z.object({ port: z.number(), host: z.string().nullable() }).strict()");
+}
+
+#[test]
+fn subtype_and_diff() {
+  let open = Open::shape(&ShapeOptions::for_deserialize());
+  let config = Config::shape(&ShapeOptions::for_deserialize());
+  assert!(!open.is_subtype_of(&config));
+  assert!(config.is_subtype_of(&open));
+
+  let changes = diff(&open, &config, ShapeOptionsKind::Deserialize);
+  eq!(
+    changes.to_string(),
+    "[breaking] /: object made exact\n[non-breaking] /host: optional property added\n"
+  );
+}
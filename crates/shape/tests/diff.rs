@@ -0,0 +1,136 @@
+mod common;
+
+use shape::{diff, Change, ChangeKind, Shape, ShapeOptions, ShapeOptionsKind, Type};
+
+mod v1 {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    pub id: u64,
+    pub name: String,
+    pub email: Option<String>,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[serde(tag = "type")]
+  pub enum Event {
+    Created { user: User },
+    Deleted { id: u64 },
+  }
+}
+
+mod v2 {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub nickname: String,
+    pub age: u8,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[serde(tag = "type")]
+  pub enum Event {
+    Created { user: User },
+    Renamed { id: u64, name: String },
+  }
+}
+
+#[test]
+fn no_changes() {
+  let ty = v1::User::shape(&ShapeOptions::for_serialize());
+  let diff = diff(&ty, &ty, ShapeOptionsKind::Serialize);
+  assert!(diff.is_empty());
+  assert!(!diff.is_breaking());
+}
+
+#[test]
+fn serialize() {
+  let old = v1::User::shape(&ShapeOptions::for_serialize());
+  let new = v2::User::shape(&ShapeOptions::for_serialize());
+  let diff = diff(&old, &new, ShapeOptionsKind::Serialize);
+
+  eq!(
+    diff.changes,
+    vec![
+      Change { path: "/email".into(), kind: ChangeKind::PropertyRemoved { optional: false }, breaking: true },
+      Change { path: "/nickname".into(), kind: ChangeKind::PropertyAdded { optional: false }, breaking: false },
+      Change { path: "/age".into(), kind: ChangeKind::PropertyAdded { optional: false }, breaking: false },
+    ]
+  );
+}
+
+#[test]
+fn deserialize() {
+  let old = v1::User::shape(&ShapeOptions::for_deserialize());
+  let new = v2::User::shape(&ShapeOptions::for_deserialize());
+  let diff = diff(&old, &new, ShapeOptionsKind::Deserialize);
+
+  eq!(
+    diff.changes,
+    vec![
+      Change { path: "/email".into(), kind: ChangeKind::PropertyRemoved { optional: true }, breaking: false },
+      Change { path: "/nickname".into(), kind: ChangeKind::PropertyAdded { optional: true }, breaking: false },
+      Change { path: "/age".into(), kind: ChangeKind::PropertyAdded { optional: false }, breaking: true },
+    ]
+  );
+
+  eq!(
+    diff.to_string(),
+    "[non-breaking] /email: optional property removed\n[non-breaking] /nickname: optional property added\n[breaking] /age: required property added\n"
+  );
+}
+
+#[test]
+fn variants() {
+  let old = v1::Event::shape(&ShapeOptions::for_serialize());
+  let new = v2::Event::shape(&ShapeOptions::for_serialize());
+  let changes = diff(&old, &new, ShapeOptionsKind::Serialize).changes;
+
+  let summary = changes.iter().map(|c| (c.path.as_str(), c.breaking)).collect::<Vec<_>>();
+  eq!(
+    summary,
+    vec![
+      ("#Created/user/email", true),
+      ("#Created/user/nickname", false),
+      ("#Created/user/age", false),
+      ("#Deleted", true),
+      ("#Renamed", true),
+    ]
+  );
+  eq!(changes[3].kind, ChangeKind::VariantRemoved);
+  eq!(changes[4].kind, ChangeKind::VariantAdded);
+
+  let old = v1::Event::shape(&ShapeOptions::for_deserialize());
+  let new = v2::Event::shape(&ShapeOptions::for_deserialize());
+  let changes = diff(&old, &new, ShapeOptionsKind::Deserialize).changes;
+  let variants = changes.iter().filter(|c| !c.path.starts_with("#Created")).map(|c| (c.path.as_str(), c.breaking)).collect::<Vec<_>>();
+  eq!(variants, vec![("#Deleted", true), ("#Renamed", false)]);
+}
+
+#[test]
+fn type_changes() {
+  let changes = diff(&Type::Number, &Type::String, ShapeOptionsKind::Serialize).changes;
+  eq!(changes, vec![Change { path: "".into(), kind: ChangeKind::TypeChanged { old: Type::Number, new: Type::String }, breaking: true }]);
+
+  // widening is safe to deserialize but not to serialize
+  let old = Option::<u32>::shape(&ShapeOptions::for_serialize());
+  let new = Option::<String>::shape(&ShapeOptions::for_serialize());
+  let changes = diff(&old, &new, ShapeOptionsKind::Serialize).changes;
+  eq!(changes[0].kind, ChangeKind::TypeChanged { old: Type::Number, new: Type::String });
+
+  let narrow = Type::Number;
+  let wide = Type::Or(vec![Type::Number, Type::Null]);
+  assert!(diff(&narrow, &wide, ShapeOptionsKind::Serialize).is_breaking());
+  assert!(!diff(&narrow, &wide, ShapeOptionsKind::Deserialize).is_breaking());
+  assert!(!diff(&wide, &narrow, ShapeOptionsKind::Serialize).is_breaking());
+  assert!(diff(&wide, &narrow, ShapeOptionsKind::Deserialize).is_breaking());
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use serde_json::json;
+use shape::{diff, json_schema, Definitions, SchemaDocument, Shape, ShapeOptions, ShapeOptionsKind, Type, TypescriptExporter};
+
+/// A user of the app
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  /// The id of the user
+  id: u64,
+  /// The name shown in the app,
+  ///
+  /// never empty
+  name: String,
+  role: Role,
+}
+
+/// What a user can do
+#[allow(unused)]
+#[derive(Shape)]
+enum Role {
+  /// Can do everything
+  Admin { level: u8 },
+  Guest,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "type")]
+enum Event {
+  /// A user signed in
+  SignIn { id: u64 },
+  /// A user signed out
+  SignOut,
+}
+
+fn property(ty: &Type, key: &str) -> Option<String> {
+  match ty {
+    Type::Object(object) => object.properties[key].description.clone(),
+    Type::And(types) => types.iter().find_map(|ty| match ty {
+      Type::Object(object) if object.properties.contains_key(key) => object.properties[key].description.clone(),
+      _ => None,
+    }),
+    _ => panic!("expected an object"),
+  }
+}
+
+#[test]
+fn derive() {
+  let mut definitions = Definitions::new();
+  definitions.add::<User>(&ShapeOptions::for_serialize());
+  eq!(definitions.description("User"), Some("A user of the app"));
+  eq!(definitions.description("Role"), Some("What a user can do"));
+
+  let user = definitions.get("User").unwrap();
+  eq!(property(user, "id"), Some(String::from("The id of the user")));
+  eq!(property(user, "name"), Some(String::from("The name shown in the app,\n\nnever empty")));
+  eq!(property(user, "role"), None);
+
+  // the doc of a variant goes on its key or on its tag
+  let Type::Or(roles) = definitions.get("Role").unwrap() else { panic!("expected a union") };
+  eq!(property(&roles[0], "Admin"), Some(String::from("Can do everything")));
+
+  let Type::Or(events) = Event::shape(&ShapeOptions::for_serialize()) else { panic!("expected a union") };
+  eq!(property(&events[0], "type"), Some(String::from("A user signed in")));
+  eq!(property(&events[1], "type"), Some(String::from("A user signed out")));
+}
+
+#[test]
+fn typescript() {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<User>();
+  eq!(
+    exporter.export(),
+    "/** What a user can do */\n\
+     export type Role = ({ /** Can do everything */ Admin: { level: number; }; } | \"Guest\");\n\
+     /** A user of the app */\n\
+     export type User = { /** The id of the user */ id: number; /**\n * The name shown in the app,\n *\n * never empty\n */ name: string; role: Role; };\n"
+  );
+
+  let zod = exporter.export_zod();
+  assert!(zod.contains("id: z.number().describe(\"The id of the user\")"), "{}", zod);
+  assert!(zod.contains("export const User: z.ZodType<User> = z.object({"), "{}", zod);
+  assert!(zod.contains(".describe(\"A user of the app\");\n"), "{}", zod);
+}
+
+#[test]
+fn json_schema_description() {
+  let schema = json_schema::<User>(&ShapeOptions::for_serialize());
+  eq!(schema["$defs"]["User"]["description"], json!("A user of the app"));
+  eq!(schema["$defs"]["User"]["properties"]["id"]["description"], json!("The id of the user"));
+  eq!(schema["$defs"]["User"]["properties"]["role"].get("description"), None);
+}
+
+#[test]
+fn document() {
+  let mut document = SchemaDocument::new();
+  document.add::<User>();
+  let json = document.to_json();
+  let loaded = SchemaDocument::from_json(&json).unwrap();
+  eq!(loaded.serialize.description("User"), Some("A user of the app"));
+  eq!(loaded.to_json(), json);
+}
+
+#[test]
+fn docs_are_not_changes() {
+  let with_docs = User::shape(&ShapeOptions::for_serialize());
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct User {
+    id: u64,
+    name: String,
+    role: Role,
+  }
+
+  let without_docs = User::shape(&ShapeOptions::for_serialize());
+  assert!(diff(&with_docs, &without_docs, ShapeOptionsKind::Serialize).changes.is_empty());
+}
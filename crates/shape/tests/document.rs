@@ -0,0 +1,130 @@
+mod common;
+
+use serde_json::json;
+use shape::{Array, Literal, Object, Property, Record, SchemaDocument, SchemaLock, Shape, ShapeOptions, ToTypescript, Tuple, Type};
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  id: u64,
+  name: Option<String>,
+  #[serde(default)]
+  tags: Vec<String>,
+  role: Role,
+  pair: Pair,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Pair(u8, #[serde(skip_serializing_if = "Option::is_none")] Option<bool>);
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "type")]
+enum Role {
+  Admin { level: u8 },
+  Guest,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[shape(generic)]
+struct Page<T> {
+  items: Vec<T>,
+}
+
+fn document() -> SchemaDocument {
+  let mut document = SchemaDocument::new();
+  document.add::<User>().add_as::<Page<User>>("Users");
+  document
+}
+
+#[test]
+fn roundtrip() {
+  let document = document();
+  let json = document.to_json();
+  eq!(json["version"], json!(1));
+
+  let loaded = SchemaDocument::from_json(&json).unwrap();
+  eq!(loaded.to_json(), json);
+
+  for (name, ty) in document.serialize.iter() {
+    eq!(loaded.serialize.get(name).unwrap().to_typescript(), ty.to_typescript());
+    eq!(loaded.serialize.params(name), document.serialize.params(name));
+  }
+
+  // the lock of a loaded document is the same as the lock of the types
+  let mut lock = SchemaLock::new();
+  lock.add::<User>().add_as::<Page<User>>("Users");
+  eq!(SchemaLock::from_document(&loaded).render(), lock.render());
+}
+
+#[test]
+fn object_properties_keep_their_order() {
+  let json = document().to_json();
+  let user = json["serialize"].as_array().unwrap().iter().find(|def| def["name"] == "User").unwrap();
+  let names = user["type"]["properties"].as_array().unwrap().iter().map(|p| p["name"].as_str().unwrap()).collect::<Vec<_>>();
+  eq!(names, vec!["id", "name", "tags", "role", "pair"]);
+}
+
+#[test]
+fn invalid_documents() {
+  let err = SchemaDocument::from_json(&json!({ "version": 2, "serialize": [], "deserialize": [] })).unwrap_err();
+  eq!(err.path, "/version");
+
+  let err = SchemaDocument::from_json(&json!({
+    "version": 1,
+    "serialize": [{ "name": "A", "params": [], "type": { "kind": "array", "item": { "kind": "nope" } } }],
+    "deserialize": [],
+  }))
+  .unwrap_err();
+  eq!(err.to_string(), "invalid schema document at /serialize/0/type/item/kind: unknown kind \"nope\"");
+
+  let document = SchemaDocument::from_json(&json!({
+    "version": 1,
+    "serialize": [{ "name": "A", "params": [], "type": { "kind": "literal", "value": 1 } }],
+    "deserialize": [],
+  }))
+  .unwrap();
+  eq!(document.serialize.get("A"), Some(&Type::Literal(shape::Literal::Number(1.0))));
+}
+
+#[test]
+fn serde() {
+  let ty = User::shape(&ShapeOptions::for_serialize());
+  let json = serde_json::to_value(&ty).unwrap();
+  eq!(serde_json::from_value::<Type>(json.clone()).unwrap(), ty);
+
+  let Type::Object(object) = ty else { panic!("expected an object") };
+  eq!(serde_json::to_value(&object).unwrap(), json);
+  eq!(serde_json::from_value::<Object>(json).unwrap(), object);
+
+  let prop = object.properties["name"].clone();
+  eq!(
+    serde_json::to_value(&prop).unwrap(),
+    json!({ "optional": false, "readonly": false, "type": { "kind": "or", "types": [{ "kind": "string" }, { "kind": "null" }] } })
+  );
+  eq!(serde_json::from_value::<Property>(serde_json::to_value(&prop).unwrap()).unwrap(), prop);
+
+  eq!(serde_json::to_value(Literal::String("a".into())).unwrap(), json!("a"));
+  eq!(serde_json::from_value::<Literal>(json!(true)).unwrap(), Literal::Boolean(true));
+
+  let tuple = Tuple { items: vec![Type::Number, Type::String], optional: 1, rest: Some(Box::new(Type::Boolean)) };
+  eq!(serde_json::from_value::<Tuple>(serde_json::to_value(&tuple).unwrap()).unwrap(), tuple);
+
+  let array = Array { item: Box::new(Type::Custom("Date".into())) };
+  eq!(serde_json::to_value(&array).unwrap(), json!({ "kind": "array", "item": { "kind": "custom", "name": "Date" } }));
+  eq!(serde_json::from_value::<Array>(serde_json::to_value(&array).unwrap()).unwrap(), array);
+
+  let record = Record { optional: true, readonly: false, key: Box::new(Type::Ref("Kind".into())), value: Box::new(Type::Number) };
+  eq!(serde_json::from_value::<Record>(serde_json::to_value(&record).unwrap()).unwrap(), record);
+
+  // a component only reads its own kind
+  let err = serde_json::from_value::<Array>(json!({ "kind": "string" })).unwrap_err();
+  eq!(err.to_string(), "invalid schema document at /kind: expected \"array\"");
+
+  let document = document();
+  let json = serde_json::to_string(&document).unwrap();
+  eq!(serde_json::from_str::<serde_json::Value>(&json).unwrap(), document.to_json());
+  eq!(serde_json::from_str::<SchemaDocument>(&json).unwrap().to_json(), document.to_json());
+}
@@ -0,0 +1,152 @@
+use shape::{Shape, ShapeOptions, ToTypescript, TypescriptExporter};
+use text_diff::print_diff;
+
+macro_rules! eq {
+  ($a:expr, $b:expr) => {
+    let a = $a;
+    let b = $b;
+    if a != b {
+      print_diff(&a, &b, "\n");
+      panic!("$a != $b");
+    }
+  };
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  id: u64,
+  name: String,
+  role: Role,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+enum Role {
+  Admin,
+  Guest,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Tree {
+  value: u32,
+  children: Vec<Tree>,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Page {
+  users: Vec<User>,
+  next: Option<String>,
+}
+
+#[test]
+fn export_collects_dependencies() {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Page>();
+
+  eq!(
+    exporter.export(),
+    r#"export type Page = { users: Array<User>; next: (string | null); };
+export type Role = ("Admin" | "Guest");
+export type User = { id: number; name: string; role: Role; };
+"#
+  );
+}
+
+#[test]
+fn export_order_is_stable() {
+  let mut a = TypescriptExporter::new(ShapeOptions::for_serialize());
+  a.add::<User>().add::<Page>();
+
+  let mut b = TypescriptExporter::new(ShapeOptions::for_serialize());
+  b.add::<Page>().add::<User>();
+
+  eq!(a.export(), b.export());
+}
+
+#[test]
+fn export_recursive() {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Tree>();
+
+  eq!(
+    exporter.export(),
+    "export type Tree = { value: number; children: Array<Tree>; };\n"
+  );
+}
+
+#[test]
+fn export_as() {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_deserialize());
+  exporter.add_as::<Vec<User>>("Users");
+  exporter.add::<String>();
+
+  eq!(
+    exporter.export(),
+    r#"export type Role = ("Admin" | "Guest");
+export type User = { id: number; name: string; role: Role; };
+export type Users = Array<User>;
+"#
+  );
+}
+
+#[test]
+fn export_generics() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[shape(generic)]
+  struct Page<T> {
+    items: Vec<T>,
+    next: Option<String>,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[shape(generic)]
+  struct Tree<T> {
+    value: T,
+    children: Vec<Tree<T>>,
+  }
+
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Page<User>>().add::<Page<Vec<u8>>>().add_as::<Page<Tree<u8>>>("Forest");
+
+  eq!(
+    exporter.export(),
+    r#"export type Forest = Page<Tree<number>>;
+export type Page<T> = { items: Array<T>; next: (string | null); };
+export type Role = ("Admin" | "Guest");
+export type Tree<T> = { value: T; children: Array<Tree<T>>; };
+export type User = { id: number; name: string; role: Role; };
+"#
+  );
+
+  eq!(
+    exporter.export_zod(),
+    r#"import { z } from "zod";
+
+export type Forest = Page<Tree<number>>;
+export const Forest: z.ZodType<Forest> = z.lazy(() => Page(z.lazy(() => Tree(z.number()))));
+
+export type Page<T> = { items: Array<T>; next: (string | null); };
+export const Page = <T extends z.ZodTypeAny>(T: T) => z.object({ items: z.array(T), next: z.string().nullable() });
+
+export type Role = ("Admin" | "Guest");
+export const Role: z.ZodType<Role> = z.union([z.literal("Admin"), z.literal("Guest")]);
+
+export type Tree<T> = { value: T; children: Array<Tree<T>>; };
+export const Tree = <T extends z.ZodTypeAny>(T: T) => z.object({ value: T, children: z.array(z.lazy(() => Tree(T))) });
+
+export type User = { id: number; name: string; role: Role; };
+export const User: z.ZodType<User> = z.object({ id: z.number(), name: z.string(), role: z.lazy(() => Role) });
+"#
+  );
+
+  // the shape of an instance is not affected
+  eq!(
+    Page::<u8>::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ items: Array<number>; next: (string | null); }"
+  );
+}
@@ -1,7 +1,8 @@
 mod common;
 
 use indexmap::IndexMap;
-use shape::{Object, Property, Shape, ShapeOptions, Type};
+use serde_json::json;
+use shape::{Array, Definitions, Generic, Object, Property, Shape, ShapeOptions, ToTypescript, Type, TypescriptExporter, Validate};
 
 #[test]
 fn generics() {
@@ -19,8 +20,10 @@ fn generics() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Generic::<String>::shape(&ShapeOptions::for_serialize()), expected);
@@ -41,9 +44,118 @@ fn generics_with_where_clause() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Generic::<String>::shape(&ShapeOptions::for_serialize()), expected);
-}
\ No newline at end of file
+}
+#[test]
+fn generic_templates() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  #[shape(generic)]
+  struct Page<T> {
+    items: Vec<T>,
+  }
+
+  let mut definitions = Definitions::with_generics();
+  let ty = definitions.add::<Page<u8>>(&ShapeOptions::for_serialize());
+  eq!(ty, Type::Generic(Generic { name: "Page".into(), args: vec![Type::Number] }));
+  eq!(definitions.params("Page"), Some(&["T".to_string()][..]));
+
+  let template = Type::Object(Object {
+    properties: IndexMap::from([(
+      "items".into(),
+      Property { ty: Type::Array(Array { item: Box::new(Type::Param("T".into())) }), optional: false, readonly: false, description: None },
+    )]),
+    exact: false,
+  });
+  eq!(definitions.get("Page"), Some(&template));
+
+  // every instance uses the same template
+  eq!(
+    definitions.add::<Page<String>>(&ShapeOptions::for_serialize()),
+    Type::Generic(Generic { name: "Page".into(), args: vec![Type::String] })
+  );
+  eq!(definitions.len(), 1);
+
+  eq!(definitions.resolve(&ty), Page::<u8>::shape(&ShapeOptions::for_serialize()));
+  assert!(ty.validate_with(&json!({ "items": [1, 2] }), &definitions).is_ok());
+  assert!(ty.validate_with(&json!({ "items": ["a"] }), &definitions).is_err());
+}
+
+#[test]
+fn bounded_generic_fields() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Sorted<T>(Vec<T>)
+  where
+    T: Ord;
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Page<T>
+  where
+    T: Ord,
+  {
+    s: Sorted<T>,
+  }
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Borrowed<'a, T>
+  where
+    T: Clone,
+  {
+    c: std::borrow::Cow<'a, T>,
+  }
+
+  eq!(Page::<u8>::shape(&ShapeOptions::for_serialize()).to_typescript(), "{ s: Array<number>; }");
+  eq!(Borrowed::<u8>::shape(&ShapeOptions::for_serialize()).to_typescript(), "{ c: number; }");
+
+  // without #[shape(generic)] every instance is a separate definition
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Page<u8>>();
+  eq!(exporter.export(), "export type Page_u8 = { s: Sorted_u8; };\nexport type Sorted_u8 = Array<number>;\n");
+}
+
+#[test]
+fn template_optional_fields() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  #[shape(generic)]
+  struct Wrap<T> {
+    v: T,
+  }
+
+  // a missing `v` deserializes as None only when T is an Option, the template can't say that
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_deserialize());
+  exporter.add::<Wrap<u8>>().add_as::<Wrap<Option<u8>>>("W");
+  eq!(
+    exporter.export(),
+    "export type W = Wrap_Option_u8;\n\
+     export type Wrap<T> = { v: T; };\n\
+     export type Wrap_Option_u8 = { v?: (number | null | undefined); };\n"
+  );
+}
+
+#[test]
+fn monomorphize() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  #[shape(generic)]
+  struct Tree<T> {
+    value: T,
+    children: Vec<Tree<T>>,
+  }
+
+  let mut definitions = Definitions::with_generics();
+  let ty = definitions.add::<Tree<u8>>(&ShapeOptions::for_serialize());
+  let (ty, instances) = definitions.monomorphize(&ty);
+  eq!(ty, Type::Ref("Tree<number>".into()));
+  eq!(instances.iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!["Tree<number>"]);
+  eq!(instances.get("Tree<number>").unwrap().to_typescript(), "{ value: number; children: Array<Tree_number>; }");
+}
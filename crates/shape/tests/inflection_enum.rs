@@ -32,13 +32,17 @@ fn snake_upper() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     })
   ]);
 
@@ -74,13 +78,17 @@ fn screaming_snake_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -116,13 +124,17 @@ fn lowercase() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     })
   ]);
 
@@ -158,13 +170,17 @@ fn camel_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     })
   ]);
 
@@ -200,13 +216,17 @@ fn pascal_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
-            }),       
+              exact: false,
+            }),
+            description: None,       
           },
         ),
       ]),
+      exact: false,
     })
   ]);
 
@@ -242,13 +262,17 @@ fn screaming_kebab_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     })
   ]);
 
@@ -284,13 +308,17 @@ fn kebab_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -326,13 +354,17 @@ fn uppercase() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },      
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -370,13 +402,17 @@ fn rename_all_fields_snake_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
-            })
+              exact: false,
+            }),
+            description: None,
           }
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -413,13 +449,17 @@ fn rename_all_fields_screaming_snake_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
-            })
+              exact: false,
+            }),
+            description: None,
           }  
         ),
-      ])
+      ]),
+      exact: false,
     })   
   ]);
 
@@ -456,14 +496,18 @@ fn rename_all_fields_lowercase() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
             
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -500,13 +544,17 @@ fn rename_all_fields_uppercase() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -542,14 +590,18 @@ fn rename_all_fields_camel_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
             
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -586,13 +638,17 @@ fn rename_all_fields_screaming_kebab_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -612,13 +668,17 @@ fn rename_all_fields_screaming_kebab_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -655,13 +715,17 @@ fn rename_all_fields_kebab_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -698,13 +762,17 @@ fn rename_all_fields_pascal_case() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -740,14 +808,18 @@ fn variant_rename_all() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
 
           },      
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -767,13 +839,17 @@ fn variant_rename_all() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
-      ])
+      ]),
+      exact: false,
     })
   ]);
 
@@ -24,9 +24,11 @@ fn snake_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(Snake::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -51,9 +53,11 @@ fn kebab_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(Kebab::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -79,9 +83,11 @@ fn screaming_snake_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(ScreamingSnake::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -107,9 +113,11 @@ fn pascal_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(Pascal::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -135,9 +143,11 @@ fn camel_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(Camel::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -163,9 +173,11 @@ fn screaming_kebab_case() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(ScreamingKebab::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -191,9 +203,11 @@ fn uppercase() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
-    ])
+    ]),
+    exact: false,
   });
 
   eq!(Uppercase::shape(&shape::ShapeOptions::for_serialize()), expected);
@@ -219,9 +233,11 @@ fn lowercase() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(Lowercase::shape(&ShapeOptions::for_serialize()), expected);
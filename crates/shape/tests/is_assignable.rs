@@ -43,42 +43,49 @@ fn tuple() {
   assert!(Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: None,
+    optional: 0,
   })
   .is_assignable(&json!(["a", 1])));
 
   assert!(Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: Some(Box::new(Type::Boolean)),
+    optional: 0,
   })
   .is_assignable(&json!(["a", 1, true, false])));
 
   assert!(Type::Tuple(Tuple {
     items: vec![Type::Literal(Literal::String("a".into())), Type::Number],
     rest: Some(Box::new(Type::Boolean)),
+    optional: 0,
   })
   .is_assignable(&json!(["a", 1, true, false])));
 
   assert!(!Type::Tuple(Tuple {
     items: vec![Type::Literal(Literal::String("a".into())), Type::Number],
     rest: Some(Box::new(Type::Boolean)),
+    optional: 0,
   })
   .is_assignable(&json!(["b", 1, true, false])));
 
   assert!(!Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: Some(Box::new(Type::Boolean)),
+    optional: 0,
   })
   .is_assignable(&json!(["a", 1, 2, false])));
 
   assert!(!Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: None,
+    optional: 0,
   })
   .is_assignable(&json!([1])));
 
   assert!(!Type::Tuple(Tuple {
     items: vec![],
     rest: None,
+    optional: 0,
   }).is_assignable(&json!(true)));
 }
 
@@ -87,9 +94,10 @@ fn objects() {
   // Caso 1: Objeto con propiedades obligatorias.
   let obj_type = Type::Object(Object {
     properties: indexmap! {
-        "name".to_string() => Property { ty: Type::String, optional: false, readonly: false },
-        "age".to_string() => Property { ty: Type::Number, optional: false, readonly: false },
+        "name".to_string() => Property { ty: Type::String, optional: false, readonly: false, description: None },
+        "age".to_string() => Property { ty: Type::Number, optional: false, readonly: false, description: None },
     },
+    exact: false,
   });
   assert!(obj_type.is_assignable(&json!({ "name": "Alice", "age": 30 })));
   assert!(!obj_type.is_assignable(&json!({ "name": "Alice" }))); // Falta "age".
@@ -97,9 +105,10 @@ fn objects() {
   // Caso 2: Propiedad opcional.
   let obj_type = Type::Object(Object {
     properties: indexmap! {
-        "name".to_string() => Property { ty: Type::String, optional: false, readonly: false },
-        "age".to_string() => Property { ty: Type::Number, optional: true, readonly: false },
+        "name".to_string() => Property { ty: Type::String, optional: false, readonly: false, description: None },
+        "age".to_string() => Property { ty: Type::Number, optional: true, readonly: false, description: None },
     },
+    exact: false,
   });
   assert!(obj_type.is_assignable(&json!({ "name": "Alice" }))); // "age" opcional.
   assert!(obj_type.is_assignable(&json!({ "name": "Alice", "age": 30 })));
@@ -107,16 +116,18 @@ fn objects() {
   // Caso 3: Propiedad de solo lectura.
   let obj_type = Type::Object(Object {
     properties: indexmap! {
-        "id".to_string() => Property { ty: Type::Number, optional: false, readonly: true },
+        "id".to_string() => Property { ty: Type::Number, optional: false, readonly: true, description: None },
     },
+    exact: false,
   });
   assert!(obj_type.is_assignable(&json!({ "id": 42 }))); // "readonly" no afecta asignabilidad.
 
   // Caso 4: Propiedad con un tipo compuesto.
   let obj_type = Type::Object(Object {
     properties: indexmap! {
-        "data".to_string() => Property { ty: Type::Array(Array { item: Box::new(Type::String) }), optional: false, readonly: false },
+        "data".to_string() => Property { ty: Type::Array(Array { item: Box::new(Type::String) }), optional: false, readonly: false, description: None },
     },
+    exact: false,
   });
   assert!(obj_type.is_assignable(&json!({ "data": ["a", "b", "c"] })));
   assert!(!obj_type.is_assignable(&json!({ "data": ["a", 1, "c"] }))); // Tipo incorrecto en el array.
@@ -215,7 +226,8 @@ fn records() {
     value: Box::new(Type::Number),
   });
   assert!(record_type.is_assignable(&json!({})));
-  assert!(record_type.is_assignable(&json!({ "a": 42 })));
+  // no key is both "a" and "b"
+  assert!(!record_type.is_assignable(&json!({ "a": 42 })));
 
   let record_type = Type::Record(Record {
     optional: false,
@@ -272,7 +284,7 @@ fn records() {
   let record_type = Type::Record(Record {
     optional: false,
     readonly: false,
-    key: Box::new(Type::Object(Object { properties: indexmap! {} })),
+    key: Box::new(Type::Object(Object { properties: indexmap! {}, exact: false })),
     value: Box::new(Type::String),
   });
   assert!(!record_type.is_assignable(&json!({})));
@@ -304,7 +316,7 @@ fn records() {
   let record_type = Type::Record(Record {
     optional: false,
     readonly: false,
-    key: Box::new(Type::Tuple(Tuple{ items: vec![Type::String, Type::Number], rest: None })),
+    key: Box::new(Type::Tuple(Tuple{ items: vec![Type::String, Type::Number], rest: None, optional: 0 })),
     value: Box::new(Type::String),
   });
   assert!(!record_type.is_assignable(&json!({})));
@@ -347,13 +359,15 @@ fn and_type() {
   let and_type = Type::And(vec![
     Type::Object(Object {
       properties: indexmap! {
-          "key".to_string() => Property { ty: Type::String, optional: false, readonly: false },
+          "key".to_string() => Property { ty: Type::String, optional: false, readonly: false, description: None },
       },
+      exact: false,
     }),
     Type::Object(Object {
       properties: indexmap! {
-          "value".to_string() => Property { ty: Type::Number, optional: false, readonly: false },
+          "value".to_string() => Property { ty: Type::Number, optional: false, readonly: false, description: None },
       },
+      exact: false,
     }),
   ]);
   assert!(and_type.is_assignable(&json!({ "key": "test", "value": 123 })));
@@ -394,13 +408,15 @@ fn or_type() {
   let or_type = Type::Or(vec![
     Type::Object(Object {
       properties: indexmap! {
-          "optionalKey".to_string() => Property { ty: Type::String, optional: false, readonly: false },
+          "optionalKey".to_string() => Property { ty: Type::String, optional: false, readonly: false, description: None },
       },
+      exact: false,
     }),
     Type::Object(Object {
       properties: indexmap! {
-          "requiredKey".to_string() => Property { ty: Type::Number, optional: false, readonly: false },
+          "requiredKey".to_string() => Property { ty: Type::Number, optional: false, readonly: false, description: None },
       },
+      exact: false,
     }),
   ]);
   assert!(or_type.is_assignable(&json!({ "optionalKey": "value" }))); // Cumple con el primer tipo.
@@ -425,4 +441,44 @@ fn or_type() {
 fn custom() {
   let custom_type = Type::Custom("a".into());
   assert!(!custom_type.is_assignable(&json!("a")));
-}
\ No newline at end of file
+}
+#[test]
+fn partial_record() {
+  let record_type = Type::Record(Record {
+    optional: true,
+    readonly: false,
+    key: Box::new(Type::Or(vec![Type::Literal(Literal::String("a".into())), Type::Literal(Literal::String("b".into()))])),
+    value: Box::new(Type::Number),
+  });
+  assert!(record_type.is_assignable(&json!({})));
+  assert!(record_type.is_assignable(&json!({ "b": 1 })));
+  assert!(!record_type.is_assignable(&json!({ "a": "1" })));
+  assert!(!record_type.is_assignable(&json!({ "c": 1 })));
+}
+
+#[test]
+fn bool_keys() {
+  use shape::{Shape, ShapeOptions, Validate};
+
+  let map = std::collections::HashMap::<bool, u8>::shape(&ShapeOptions::for_deserialize());
+  for value in [json!({}), json!({ "true": 1 }), json!({ "true": 1, "false": 0 })] {
+    assert!(map.is_assignable(&value), "{}", value);
+    assert!(map.validate(&value).is_ok(), "{}", value);
+  }
+  for value in [json!({ "yes": 1 }), json!({ "true": "1" })] {
+    assert!(!map.is_assignable(&value), "{}", value);
+    assert!(map.validate(&value).is_err(), "{}", value);
+  }
+
+  // both booleans are required when the record isn't partial
+  let record_type = Type::Record(Record {
+    optional: false,
+    readonly: false,
+    key: Box::new(Type::Boolean),
+    value: Box::new(Type::Number),
+  });
+  assert!(record_type.is_assignable(&json!({ "true": 1, "false": 0 })));
+  assert!(record_type.validate(&json!({ "true": 1, "false": 0 })).is_ok());
+  assert!(!record_type.is_assignable(&json!({ "true": 1 })));
+  assert!(record_type.validate(&json!({ "true": 1 })).is_err());
+}
@@ -0,0 +1,98 @@
+use shape::{line_diff, LockError, SchemaLock, Shape};
+use text_diff::print_diff;
+
+macro_rules! eq {
+  ($a:expr, $b:expr) => {
+    let a = $a;
+    let b = $b;
+    if a != b {
+      print_diff(&a, &b, "\n");
+      panic!("$a != $b");
+    }
+  };
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  id: u64,
+  name: Option<String>,
+}
+
+mod v2 {
+  use shape::Shape;
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  pub struct User {
+    id: u64,
+    name: Option<String>,
+    email: String,
+  }
+}
+
+fn lock_path(name: &str) -> std::path::PathBuf {
+  let dir = std::env::temp_dir().join(format!("shape-lock-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  dir.join(name)
+}
+
+#[test]
+fn render() {
+  let mut lock = SchemaLock::new();
+  lock.add::<User>();
+
+  eq!(
+    lock.render(),
+    "// This file is generated by shape, do not edit it by hand
+
+export namespace Serialize {
+  export type User = { id: number; name: (string | null); };
+}
+
+export namespace Deserialize {
+  export type User = { id: number; name?: (string | null | undefined); };
+}
+"
+  );
+}
+
+#[test]
+fn check() {
+  let path = lock_path("check.ts");
+  let mut lock = SchemaLock::new();
+  lock.add::<User>();
+
+  // a missing lock file is a mismatch
+  assert!(matches!(lock.check(&path), Err(LockError::Mismatch { .. })));
+
+  lock.write(&path).unwrap();
+  lock.check(&path).unwrap();
+
+  let mut changed = SchemaLock::new();
+  changed.add::<v2::User>();
+  match changed.check(&path) {
+    Err(LockError::Mismatch { diff }) => {
+      eq!(
+        diff,
+        "  export namespace Serialize {
+-   export type User = { id: number; name: (string | null); };
++   export type User = { id: number; name: (string | null); email: string; };
+  }
+  
+  export namespace Deserialize {
+-   export type User = { id: number; name?: (string | null | undefined); };
++   export type User = { id: number; name?: (string | null | undefined); email: string; };
+  }
+"
+      );
+    }
+    other => panic!("expected a mismatch, found {:?}", other),
+  }
+}
+
+#[test]
+fn diff_lines() {
+  eq!(line_diff("a\nb\nc\n", "a\nb\nc\n"), "");
+  eq!(line_diff("a\nb\nc\nd\n", "a\nc\nd\ne\n"), "  a\n- b\n  c\n  d\n+ e\n");
+}
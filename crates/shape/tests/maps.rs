@@ -31,4 +31,36 @@ fn maps() {
       key: Box::new(Type::String),
       value: Box::new(Type::Number),
   }));
-}
\ No newline at end of file
+}
+#[test]
+fn derived_keys() {
+  use serde_json::json;
+  use shape::{json_schema, TypescriptExporter, Validate};
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Id(u64);
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Names {
+    names: HashMap<Id, String>,
+  }
+
+  // keys are the strings of the numbers and not the schema of `Id`
+  let schema = json_schema::<Names>(&ShapeOptions::for_serialize());
+  eq!(schema["$defs"]["Id"], json!({ "type": "number" }));
+  eq!(
+    schema["$defs"]["Names"]["properties"]["names"]["propertyNames"],
+    json!({ "type": "string", "pattern": r"^-?\d+(\.\d+)?([eE][+-]?\d+)?$" })
+  );
+
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Names>();
+  let zod = exporter.export_zod();
+  assert!(zod.contains(r"names: z.record(z.string().regex(/^-?\d+(\.\d+)?([eE][+-]?\d+)?$/), z.string())"), "{}", zod);
+
+  let mut definitions = shape::Definitions::new();
+  let ty = definitions.add::<Names>(&ShapeOptions::for_serialize());
+  eq!(ty.validate_with(&json!({ "names": { "1": "a" } }), &definitions), Ok(()));
+}
@@ -21,6 +21,7 @@ fn simple_struct() {
           ty: Type::Boolean,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -29,6 +30,7 @@ fn simple_struct() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -37,9 +39,11 @@ fn simple_struct() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(Simple::shape(&ShapeOptions::for_serialize()), expected);
@@ -81,6 +85,7 @@ fn struct_with_serde_attrs() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -89,6 +94,7 @@ fn struct_with_serde_attrs() {
           ty: Type::Boolean,
           optional: true,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -102,6 +108,7 @@ fn struct_with_serde_attrs() {
                   ty: Type::String,
                   optional: false,
                   readonly: false,
+                  description: None,
                 },
               ),
               (
@@ -110,15 +117,19 @@ fn struct_with_serde_attrs() {
                   ty: Type::Number,
                   optional: false,
                   readonly: false,
+                  description: None,
                 },
               ),
             ]),
+            exact: false,
           }),
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(SerdeTest::shape(&ShapeOptions::for_serialize()), expected);
@@ -150,8 +161,10 @@ fn struct_with_flatten() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
     Type::Object(Object {
       properties: IndexMap::from([
@@ -161,6 +174,7 @@ fn struct_with_flatten() {
             ty: Type::Number,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -169,9 +183,11 @@ fn struct_with_flatten() {
             ty: Type::Boolean,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
   ]);
 
@@ -216,6 +232,7 @@ fn enum_untagged() {
     Type::Tuple(Tuple {
       items: vec![Type::Number, Type::String],
       rest: None,
+      optional: 0,
     }),
     Type::Object(Object {
       properties: IndexMap::from([
@@ -225,6 +242,7 @@ fn enum_untagged() {
             ty: Type::Number,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -233,9 +251,11 @@ fn enum_untagged() {
             ty: Type::String,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
   ]);
 
@@ -264,8 +284,10 @@ fn enum_internally_tagged() {
           ty: Type::Literal(Literal::String("A".into())),
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
     // newtype
     Type::And(vec![
@@ -276,8 +298,10 @@ fn enum_internally_tagged() {
             ty: Type::Literal(Literal::String("B".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
       Type::Number,
     ]),
@@ -290,12 +314,15 @@ fn enum_internally_tagged() {
             ty: Type::Literal(Literal::String("C".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
       Type::Tuple(Tuple {
         items: vec![Type::Number, Type::Boolean],
         rest: None,
+        optional: 0,
       }),
     ]),
     // named
@@ -307,8 +334,10 @@ fn enum_internally_tagged() {
             ty: Type::Literal(Literal::String("D".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
       Type::Object(Object {
         properties: IndexMap::from([
@@ -318,6 +347,7 @@ fn enum_internally_tagged() {
               ty: Type::Number,
               optional: false,
               readonly: false,
+              description: None,
             },
           ),
           (
@@ -326,9 +356,11 @@ fn enum_internally_tagged() {
               ty: Type::String,
               optional: false,
               readonly: false,
+              description: None,
             },
           ),
         ]),
+        exact: false,
       }),
     ]),
   ]);
@@ -358,8 +390,10 @@ fn enum_adjacently_tagged() {
           ty: Type::Literal(Literal::String("A".into())),
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
     // newtype
     Type::Object(Object {
@@ -370,6 +404,7 @@ fn enum_adjacently_tagged() {
             ty: Type::Literal(Literal::String("B".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -378,9 +413,11 @@ fn enum_adjacently_tagged() {
             ty: Type::Number,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
     // tuple
     Type::Object(Object {
@@ -391,6 +428,7 @@ fn enum_adjacently_tagged() {
             ty: Type::Literal(Literal::String("C".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -401,10 +439,13 @@ fn enum_adjacently_tagged() {
             ty: Type::Tuple(Tuple {
               items: vec![Type::Number, Type::Boolean],
               rest: None,
+              optional: 0,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
     // named
     Type::Object(Object {
@@ -415,6 +456,7 @@ fn enum_adjacently_tagged() {
             ty: Type::Literal(Literal::String("D".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -430,6 +472,7 @@ fn enum_adjacently_tagged() {
                     ty: Type::Number,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
                 (
@@ -438,13 +481,17 @@ fn enum_adjacently_tagged() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
   ]);
 
@@ -471,6 +518,7 @@ fn struct_with_rename() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -479,9 +527,11 @@ fn struct_with_rename() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(RenamedFields::shape(&ShapeOptions::for_serialize()), expected);
@@ -505,8 +555,10 @@ fn struct_with_skip_serializing() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(SkipSerializing::shape(&ShapeOptions::for_serialize()), expected);
@@ -529,8 +581,10 @@ fn struct_with_skip_deserializing() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(
@@ -555,8 +609,10 @@ fn struct_with_default() {
         ty: Type::Number,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   let expected_deserialize = Type::Object(Object {
@@ -566,8 +622,10 @@ fn struct_with_default() {
         ty: Type::Number,
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(
@@ -596,8 +654,10 @@ fn struct_with_skip_serializing_if() {
         ty: Type::String,
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(SkipIfField::shape(&ShapeOptions::for_serialize()), expected);
@@ -659,8 +719,10 @@ fn struct_with_skip_and_rename() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(SkipAndRename::shape(&ShapeOptions::for_serialize()), expected);
@@ -704,8 +766,10 @@ fn enum_with_skip_variant() {
         ty: Type::Literal(Literal::String("Included".into())),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   })]);
 
   eq!(EnumWithSkip::shape(&ShapeOptions::for_serialize()), expected);
@@ -730,8 +794,10 @@ fn enum_with_skip_serializing_variant() {
         ty: Type::Literal(Literal::String("AlwaysSerialized".into())),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   })]);
 
   eq!(
@@ -758,8 +824,10 @@ fn enum_with_skip_deserializing_variant() {
         ty: Type::Literal(Literal::String("AlwaysDeserialized".into())),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   })]);
 
   eq!(
@@ -788,8 +856,10 @@ fn enum_with_internal_tagging() {
           ty: Type::Literal(Literal::String("Unit".into())),
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
     // Newtype variant
     Type::And(vec![
@@ -800,8 +870,10 @@ fn enum_with_internal_tagging() {
             ty: Type::Literal(Literal::String("NewType".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
       Type::Number,
     ]),
@@ -814,8 +886,10 @@ fn enum_with_internal_tagging() {
             ty: Type::Literal(Literal::String("Struct".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
       Type::Object(Object {
         properties: IndexMap::from([(
@@ -824,8 +898,10 @@ fn enum_with_internal_tagging() {
             ty: Type::String,
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
     ]),
   ]);
@@ -859,6 +935,7 @@ fn enum_with_adjacently_tagged() {
             ty: Type::Literal(Literal::String("VariantOne".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -867,9 +944,11 @@ fn enum_with_adjacently_tagged() {
             ty: Type::Number,
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
     Type::Object(Object {
       properties: IndexMap::from([
@@ -879,6 +958,7 @@ fn enum_with_adjacently_tagged() {
             ty: Type::Literal(Literal::String("VariantTwo".into())),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
         (
@@ -891,14 +971,18 @@ fn enum_with_adjacently_tagged() {
                   ty: Type::String,
                   optional: false,
                   readonly: false,
+                  description: None,
                 },
               )]),
+              exact: false,
             }),
             optional: false,
             readonly: false,
+            description: None,
           },
         ),
       ]),
+      exact: false,
     }),
   ]);
 
@@ -953,6 +1037,7 @@ fn enum_with_flatten() {
     Type::And(vec![
       Type::Object(Object {
         properties: IndexMap::new(),
+        exact: false,
       }),
       Type::Object(Object {
         properties: IndexMap::from([(
@@ -961,8 +1046,10 @@ fn enum_with_flatten() {
             ty: Type::String,
             optional: false,
             readonly: false,
+            description: None,
           },
         )]),
+        exact: false,
       }),
     ]),
     Type::Object(Object {
@@ -972,8 +1059,10 @@ fn enum_with_flatten() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
   ]);
 
@@ -1000,11 +1089,14 @@ fn enum_with_complex_variants() {
           ty: Type::Tuple(Tuple {
             items: vec![Type::Number, Type::String],
             rest: None,
+            optional: 0,
           }),
           optional: false,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }),
     Type::Object(Object {
       properties: IndexMap::from([(
@@ -1020,6 +1112,7 @@ fn enum_with_complex_variants() {
                   optional: false,
                   readonly: false,
                   ty: Type::Number,
+                  description: None,
                 },
               ),
               (
@@ -1028,12 +1121,16 @@ fn enum_with_complex_variants() {
                   optional: false,
                   readonly: false,
                   ty: Type::Boolean,
+                  description: None,
                 },
               ),
             ]),
+            exact: false,
           }),
+          description: None,
         },
       )]),
+      exact: false,
     }),
   ]);
 
@@ -1056,8 +1153,10 @@ fn option_type_serialization() {
         ty: Type::Or(vec![Type::Number, Type::Null]),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(TestStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -1078,8 +1177,10 @@ fn option_type_deserialization() {
         ty: Type::Or(vec![Type::Number, Type::Null, Type::Undefined]),
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(TestStruct::shape(&ShapeOptions::for_deserialize()), expected);
@@ -1100,8 +1201,10 @@ fn nested_option_serialization() {
         ty: Type::Or(vec![Type::Or(vec![Type::String, Type::Null]), Type::Null]),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(NestedStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -1130,8 +1233,10 @@ fn nested_option_deserialization() {
         ]),
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(NestedStruct::shape(&ShapeOptions::for_deserialize()), expected);
@@ -1154,8 +1259,10 @@ fn option_in_enum_serialization() {
           ty: Type::Or(vec![Type::Number, Type::Null]),
           optional: false,
           readonly: false,
+          description: None,
         },
-      )])
+      )]),
+      exact: false,
     })
   ]);
 
@@ -1179,8 +1286,10 @@ fn option_in_enum_deserialization() {
           ty: Type::Or(vec![Type::Number, Type::Null, Type::Undefined]),
           optional: true,
           readonly: false,
+          description: None,
         },
       )]),
+      exact: false,
     }
   )]);
 
@@ -1204,8 +1313,10 @@ fn option_in_vec_serialization() {
         ty: Type::Array(Array {
           item: Box::new(Type::Or(vec![Type::Number, Type::Null])),   
         }),
+        description: None,
       }
-    )])
+    )]),
+    exact: false,
   });
 
   eq!(VecOptionStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -1231,9 +1342,11 @@ fn option_in_vec_deserialization() {
             Type::Null,
             Type::Undefined,
           ])),
-        })
+        }),
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(VecOptionStruct::shape(&ShapeOptions::for_deserialize()), expected);
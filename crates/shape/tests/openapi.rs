@@ -0,0 +1,145 @@
+mod common;
+
+use serde_json::json;
+use shape::{OpenApi, Shape};
+
+#[allow(unused)]
+#[derive(Shape)]
+enum Role {
+  Admin,
+  Guest,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  id: u64,
+  role: Role,
+  #[serde(default)]
+  active: bool,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct CreateUser {
+  role: Role,
+}
+
+#[test]
+fn document() {
+  let mut api = OpenApi::new("Users", "1.0.0");
+
+  api
+    .operation("POST", "/users")
+    .operation_id("createUser")
+    .summary("Creates a user")
+    .tag("users")
+    .request_body::<CreateUser>()
+    .response::<User>(201, "The created user")
+    .empty_response(400, "Invalid request");
+
+  api
+    .operation("get", "/users")
+    .response::<Vec<User>>(200, "Every user");
+
+  eq!(
+    api.document(),
+    json!({
+      "openapi": "3.1.0",
+      "info": { "title": "Users", "version": "1.0.0" },
+      "paths": {
+        "/users": {
+          "post": {
+            "operationId": "createUser",
+            "summary": "Creates a user",
+            "tags": ["users"],
+            "requestBody": {
+              "required": true,
+              "content": {
+                "application/json": {
+                  "schema": { "$ref": "#/components/schemas/CreateUser" }
+                }
+              }
+            },
+            "responses": {
+              "201": {
+                "description": "The created user",
+                "content": {
+                  "application/json": {
+                    "schema": { "$ref": "#/components/schemas/User" }
+                  }
+                }
+              },
+              "400": { "description": "Invalid request" }
+            }
+          },
+          "get": {
+            "responses": {
+              "200": {
+                "description": "Every user",
+                "content": {
+                  "application/json": {
+                    "schema": { "type": "array", "items": { "$ref": "#/components/schemas/User" } }
+                  }
+                }
+              }
+            }
+          }
+        }
+      },
+      "components": {
+        "schemas": {
+          "User": {
+            "type": "object",
+            "properties": {
+              "id": { "type": "number" },
+              "role": { "$ref": "#/components/schemas/Role" },
+              "active": { "type": "boolean" },
+            },
+            "required": ["id", "role", "active"],
+          },
+          "Role": { "anyOf": [{ "const": "Admin" }, { "const": "Guest" }] },
+          "CreateUser": {
+            "type": "object",
+            "properties": {
+              "role": { "$ref": "#/components/schemas/Role" },
+            },
+            "required": ["role"],
+          },
+        }
+      }
+    })
+  );
+}
+
+#[test]
+fn input_components() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Wrapper {
+    user: User,
+    roles: Vec<Role>,
+  }
+
+  let mut api = OpenApi::new("Users", "1.0.0");
+  api.component::<Wrapper>();
+
+  let document = api.document();
+  let schemas = document["components"]["schemas"].as_object().unwrap();
+
+  // User has a default field so its deserialize shape is different,
+  // Wrapper uses User so it's different too, Role is the same in both
+  eq!(
+    schemas.keys().cloned().collect::<Vec<_>>(),
+    vec!["Role", "User", "UserInput", "Wrapper", "WrapperInput"]
+  );
+
+  eq!(schemas["UserInput"]["required"], json!(["id", "role"]));
+  eq!(
+    schemas["WrapperInput"]["properties"],
+    json!({
+      "user": { "$ref": "#/components/schemas/UserInput" },
+      "roles": { "type": "array", "items": { "$ref": "#/components/schemas/Role" } },
+    })
+  );
+}
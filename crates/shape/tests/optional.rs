@@ -0,0 +1,47 @@
+mod common;
+
+use shape::{Shape, ShapeOptions, ToTypescript};
+
+type Maybe<T> = Option<T>;
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(transparent)]
+struct Nullable<T>(Option<T>);
+
+#[allow(unused)]
+#[derive(Shape)]
+struct Wrapper(Option<u8>);
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  alias: Maybe<String>,
+  boxed: Box<Option<u8>>,
+  nullable: Nullable<u8>,
+  // not transparent, serde requires the field
+  wrapper: Wrapper,
+  #[shape(required)]
+  required: Option<u8>,
+  #[shape(optional)]
+  tags: Vec<String>,
+}
+
+#[test]
+fn option_detection() {
+  eq!(<Option<u8> as Shape>::IS_OPTION, true);
+  eq!(<Maybe<u8> as Shape>::IS_OPTION, true);
+  eq!(<Nullable<u8> as Shape>::IS_OPTION, true);
+  eq!(<Wrapper as Shape>::IS_OPTION, false);
+  eq!(<u8 as Shape>::IS_OPTION, false);
+
+  eq!(
+    User::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "{ alias?: (string | null | undefined); boxed?: (number | null | undefined); nullable?: (number | null | undefined); wrapper: (number | null | undefined); required: (number | null | undefined); tags?: Array<string>; }"
+  );
+
+  eq!(
+    User::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ alias: (string | null); boxed: (number | null); nullable: (number | null); wrapper: (number | null); required: (number | null); tags?: Array<string>; }"
+  );
+}
@@ -53,8 +53,10 @@ fn options_is_optional() {
         ty: Type::String,
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Struct::shape(&ser_opts), expected);
@@ -92,8 +94,10 @@ fn options_add_undefined() {
         ty: Type::Or(vec![Type::String, Type::Undefined]),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Struct::shape(&ser_opts), expected);
@@ -130,8 +134,10 @@ fn options_add_null() {
         ty: Type::Or(vec![Type::String, Type::Null]),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Struct::shape(&ser_opts), expected);
@@ -168,8 +174,10 @@ fn options_add_null_and_undefined() {
         ty: Type::Or(vec![Type::String, Type::Null, Type::Undefined]),
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(Struct::shape(&ser_opts), expected);
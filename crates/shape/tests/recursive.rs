@@ -0,0 +1,262 @@
+mod common;
+
+use indexmap::IndexMap;
+use shape::{Array, Definitions, Object, Property, Shape, ShapeOptions, ToTypescript, Type};
+
+#[test]
+fn recursive_struct() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Node {
+    children: Vec<Node>,
+  }
+
+  let expected = Type::Object(Object {
+    properties: IndexMap::from([(
+      "children".into(),
+      Property {
+        ty: Type::Array(Array {
+          item: Box::new(Type::Ref("Node".into())),
+        }),
+        optional: false,
+        readonly: false,
+        description: None,
+      },
+    )]),
+    exact: false,
+  });
+
+  eq!(Node::shape(&ShapeOptions::for_serialize()), expected);
+  eq!(Node::shape(&ShapeOptions::for_deserialize()), expected);
+
+  let mut definitions = Definitions::new();
+  let root = definitions.add::<Node>(&ShapeOptions::for_serialize());
+  eq!(root, Type::Ref("Node".into()));
+  eq!(definitions.len(), 1);
+  eq!(definitions.get("Node"), Some(&expected));
+}
+
+#[test]
+fn recursive_enum() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  enum Expr {
+    Number(f64),
+    Add(Box<Expr>, Box<Expr>),
+  }
+
+  let mut definitions = Definitions::new();
+  let root = definitions.add::<Expr>(&ShapeOptions::for_serialize());
+  eq!(root, Type::Ref("Expr".into()));
+
+  let expected = Type::Or(vec![
+    Type::Object(Object {
+      properties: IndexMap::from([(
+        "Number".into(),
+        Property {
+          ty: Type::Number,
+          optional: false,
+          readonly: false,
+          description: None,
+        },
+      )]),
+      exact: false,
+    }),
+    Type::Object(Object {
+      properties: IndexMap::from([(
+        "Add".into(),
+        Property {
+          ty: Type::Tuple(shape::Tuple {
+            items: vec![Type::Ref("Expr".into()), Type::Ref("Expr".into())],
+            rest: None,
+            optional: 0,
+          }),
+          optional: false,
+          readonly: false,
+          description: None,
+        },
+      )]),
+      exact: false,
+    }),
+  ]);
+
+  eq!(definitions.get("Expr"), Some(&expected));
+  eq!(Expr::shape(&ShapeOptions::for_serialize()), expected);
+}
+
+#[test]
+fn mutually_recursive() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Parent {
+    child: Option<Box<Child>>,
+  }
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Child {
+    parent: Parent,
+  }
+
+  let options = ShapeOptions {
+    option_add_null: false,
+    ..ShapeOptions::for_serialize()
+  };
+
+  let mut definitions = Definitions::new();
+  definitions.add::<Parent>(&options);
+  eq!(definitions.iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!["Parent", "Child"]);
+
+  let expected = Type::Object(Object {
+    properties: IndexMap::from([(
+      "child".into(),
+      Property {
+        ty: Type::Object(Object {
+          properties: IndexMap::from([(
+            "parent".into(),
+            Property {
+              ty: Type::Ref("Parent".into()),
+              optional: false,
+              readonly: false,
+              description: None,
+            },
+          )]),
+          exact: false,
+        }),
+        optional: false,
+        readonly: false,
+        description: None,
+      },
+    )]),
+    exact: false,
+  });
+
+  eq!(Parent::shape(&options), expected);
+}
+
+#[test]
+fn nested_types_are_referenced() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Inner {
+    value: String,
+  }
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Outer {
+    a: Inner,
+    b: Vec<Inner>,
+  }
+
+  let mut definitions = Definitions::new();
+  let root = definitions.add::<Outer>(&ShapeOptions::for_serialize());
+  eq!(root, Type::Ref("Outer".into()));
+  eq!(definitions.len(), 2);
+
+  let outer = definitions.get("Outer").unwrap();
+  eq!(
+    outer,
+    &Type::Object(Object {
+      properties: IndexMap::from([
+        (
+          "a".into(),
+          Property {
+            ty: Type::Ref("Inner".into()),
+            optional: false,
+            readonly: false,
+            description: None,
+          },
+        ),
+        (
+          "b".into(),
+          Property {
+            ty: Type::Array(Array {
+              item: Box::new(Type::Ref("Inner".into())),
+            }),
+            optional: false,
+            readonly: false,
+            description: None,
+          },
+        ),
+      ]),
+      exact: false,
+    })
+  );
+
+  // non recursive references are inlined by resolve
+  eq!(definitions.resolve(&root), Outer::shape(&ShapeOptions::for_serialize()));
+}
+
+#[test]
+fn name_collisions() {
+  mod a {
+    #[derive(shape::Shape)]
+    #[allow(unused)]
+    pub struct Item {
+      pub a: String,
+    }
+  }
+
+  mod b {
+    #[derive(shape::Shape)]
+    #[allow(unused)]
+    pub struct Item {
+      pub b: String,
+    }
+  }
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Both {
+    a: a::Item,
+    b: b::Item,
+  }
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Reversed {
+    b: b::Item,
+    a: a::Item,
+  }
+
+  let mut definitions = Definitions::new();
+  let root = definitions.add::<(a::Item, Both)>(&ShapeOptions::for_serialize());
+  eq!(definitions.iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!["a_Item", "Both", "b_Item"]);
+  // references returned before the collision are renamed too
+  eq!(root.to_typescript(), "[a_Item, Both]");
+  eq!(definitions.get("Both").unwrap().to_typescript(), "{ a: a_Item; b: b_Item; }");
+
+  // the names don't depend on which type is seen first
+  let mut reversed = Definitions::new();
+  reversed.add::<Reversed>(&ShapeOptions::for_serialize());
+  eq!(reversed.iter().map(|(name, _)| name).collect::<Vec<_>>(), vec!["Reversed", "b_Item", "a_Item"]);
+  eq!(reversed.get("a_Item"), definitions.get("a_Item"));
+}
+
+#[test]
+fn recursive_is_assignable() {
+  use serde_json::json;
+  use shape::IsAsignable;
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Node {
+    value: u8,
+    children: Vec<Node>,
+  }
+
+  let value = json!({ "value": 1, "children": [{ "value": 2, "children": [] }] });
+  let invalid = json!({ "value": 1, "children": [{ "value": "2", "children": [] }] });
+
+  // the reference to `Node` can't be resolved without definitions
+  let ty = Node::shape(&ShapeOptions::for_serialize());
+  assert!(!ty.is_assignable(&value));
+
+  let mut definitions = Definitions::new();
+  let root = definitions.add::<Node>(&ShapeOptions::for_serialize());
+  assert!(root.is_assignable_with(&value, &definitions));
+  assert!(ty.is_assignable_with(&value, &definitions));
+  assert!(!root.is_assignable_with(&invalid, &definitions));
+  assert!(!root.is_assignable_with(&json!({ "value": 1 }), &definitions));
+}
@@ -0,0 +1,56 @@
+use shape::{registered, Shape, ShapeOptions, TypescriptExporter};
+use text_diff::print_diff;
+
+macro_rules! eq {
+  ($a:expr, $b:expr) => {
+    let a = $a;
+    let b = $b;
+    if a != b {
+      print_diff(&a, &b, "\n");
+      panic!("$a != $b");
+    }
+  };
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[shape(export)]
+struct Account<'a> {
+  id: u64,
+  owner: &'a str,
+  status: Status,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[shape(export)]
+enum Status {
+  Active,
+  Closed,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+struct NotExported {
+  id: u64,
+}
+
+#[test]
+fn registered_types() {
+  let names = registered().iter().map(|r| r.name).collect::<Vec<&str>>();
+  assert_eq!(names, vec!["Account", "Status"]);
+  assert!(registered().iter().all(|r| r.module_path == module_path!()));
+}
+
+#[test]
+fn export_registered() {
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add_registered();
+
+  eq!(
+    exporter.export(),
+    r#"export type Account = { id: number; owner: string; status: Status; };
+export type Status = ("Active" | "Closed");
+"#
+  );
+}
@@ -21,6 +21,7 @@ fn rename_all_ser_de() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -29,9 +30,11 @@ fn rename_all_ser_de() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   let de = Type::Object(Object {
@@ -42,6 +45,7 @@ fn rename_all_ser_de() {
           ty: Type::String,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
       (
@@ -50,9 +54,11 @@ fn rename_all_ser_de() {
           ty: Type::Number,
           optional: false,
           readonly: false,
+          description: None,
         },
       ),
     ]),
+    exact: false,
   });
 
   eq!(RenamedFields::shape(&ShapeOptions::for_serialize()), ser);
@@ -75,8 +81,10 @@ fn rename_ser_de() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   let de = Type::Object(Object {
@@ -86,8 +94,10 @@ fn rename_ser_de() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(RenamedFields::shape(&ShapeOptions::for_serialize()), ser);
@@ -121,13 +131,17 @@ fn enum_rename_all_fields() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           }
         )
       ]),
+      exact: false,
     })
   ]);
 
@@ -147,13 +161,17 @@ fn enum_rename_all_fields() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           }
         )
       ]),
+      exact: false,
     })
   ]);
 
@@ -209,13 +227,17 @@ fn enum_variant_rename_all() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           }
         )
       ]),
+      exact: false,
     })
   ]);
 
@@ -235,13 +257,17 @@ fn enum_variant_rename_all() {
                     ty: Type::String,
                     optional: false,
                     readonly: false,
+                    description: None,
                   },
                 ),
               ]),
+              exact: false,
             }),
+            description: None,
           }  
         ),
-      ])
+      ]),
+      exact: false,
     })   
   ]);
 
@@ -0,0 +1,58 @@
+mod common;
+
+use serde_json::json;
+use shape::{Shape, ShapeOptions, ToTypescript, Validate};
+
+#[allow(unused)]
+#[derive(Shape, serde::Deserialize)]
+#[serde(tag = "type")]
+enum Event {
+  Click { x: i32 },
+  #[serde(other)]
+  Unknown,
+}
+
+#[allow(unused)]
+#[derive(Shape, serde::Deserialize)]
+#[serde(tag = "t", content = "c")]
+enum Adjacent {
+  Value(u8),
+  #[serde(other)]
+  Other,
+}
+
+#[test]
+fn internally_tagged() {
+  eq!(
+    Event::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "(({ type: \"Click\"; } & { x: number; }) | { type: \"Unknown\"; })"
+  );
+  eq!(
+    Event::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "(({ type: \"Click\"; } & { x: number; }) | { type: string; })"
+  );
+
+  let ty = Event::shape(&ShapeOptions::for_deserialize());
+  ty.validate(&json!({ "type": "Scroll", "delta": 1 })).unwrap();
+  ty.validate(&json!({ "type": "Click", "x": 1 })).unwrap();
+
+  // the catch all can't exclude the known tags, so a known tag without its fields is accepted while serde rejects it
+  ty.validate(&json!({ "type": "Click" })).unwrap();
+  assert!(serde_json::from_value::<Event>(json!({ "type": "Click" })).is_err());
+}
+
+#[test]
+fn adjacently_tagged() {
+  eq!(
+    Adjacent::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "({ t: \"Value\"; c: number; } | { t: string; })"
+  );
+  eq!(
+    Adjacent::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "({ t: \"Value\"; c: number; } | { t: \"Other\"; })"
+  );
+
+  let ty = Adjacent::shape(&ShapeOptions::for_deserialize());
+  ty.validate(&json!({ "t": "Value" })).unwrap();
+  assert!(serde_json::from_value::<Adjacent>(json!({ "t": "Value" })).is_err());
+}
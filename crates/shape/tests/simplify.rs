@@ -0,0 +1,81 @@
+use shape::{Literal, Object, Property, Shape, ShapeOptions, Type};
+
+mod common;
+
+fn lit(s: &str) -> Type {
+  Type::Literal(Literal::String(s.into()))
+}
+
+fn object(props: &[(&str, Type)]) -> Type {
+  Type::Object(Object {
+    properties: props
+      .iter()
+      .map(|(key, ty)| (key.to_string(), Property { ty: ty.clone(), optional: false, readonly: false, description: None }))
+      .collect(),
+    exact: false,
+  })
+}
+
+#[test]
+fn unions() {
+  eq!(
+    Type::Or(vec![Type::Or(vec![Type::String, Type::Null]), Type::Undefined, Type::Never, Type::Null]).simplify(),
+    Type::Or(vec![Type::String, Type::Null, Type::Undefined])
+  );
+  eq!(Type::Or(vec![Type::Never, Type::Number]).simplify(), Type::Number);
+  eq!(Type::Or(vec![]).simplify(), Type::Never);
+  eq!(Type::Or(vec![lit("a"), Type::String, lit("b")]).simplify(), Type::String);
+  eq!(
+    Type::Or(vec![Type::Number, Type::Custom("unknown".into())]).simplify(),
+    Type::Custom("unknown".into())
+  );
+}
+
+#[test]
+fn intersections() {
+  eq!(Type::And(vec![Type::String]).simplify(), Type::String);
+  eq!(Type::And(vec![]).simplify(), Type::Never);
+  eq!(Type::And(vec![Type::String, Type::Never]).simplify(), Type::Never);
+  eq!(Type::And(vec![Type::Custom("unknown".into()), Type::Number]).simplify(), Type::Number);
+  eq!(
+    Type::And(vec![
+      object(&[("type", lit("A"))]),
+      Type::And(vec![object(&[("a", Type::Number)]), object(&[("b", Type::String)])])
+    ])
+    .simplify(),
+    object(&[("type", lit("A")), ("a", Type::Number), ("b", Type::String)])
+  );
+
+  // conflicting keys are kept apart
+  eq!(
+    Type::And(vec![object(&[("a", Type::Number)]), object(&[("a", Type::String)])]).simplify(),
+    Type::And(vec![object(&[("a", Type::Number)]), object(&[("a", Type::String)])])
+  );
+}
+
+#[test]
+fn nested() {
+  eq!(
+    object(&[("a", Type::Or(vec![Type::Or(vec![Type::Number]), Type::Never]))]).simplify(),
+    object(&[("a", Type::Number)])
+  );
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "type")]
+enum Event {
+  Click { x: i32, y: i32 },
+  Key { code: String },
+}
+
+#[test]
+fn derived() {
+  eq!(
+    Event::shape(&ShapeOptions::for_serialize()).simplify(),
+    Type::Or(vec![
+      object(&[("type", lit("Click")), ("x", Type::Number), ("y", Type::Number)]),
+      object(&[("type", lit("Key")), ("code", Type::String)]),
+    ])
+  );
+}
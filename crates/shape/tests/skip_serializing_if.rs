@@ -0,0 +1,100 @@
+mod common;
+
+use std::collections::HashMap;
+
+use serde_json::json;
+use shape::{IsAsignable, Shape, ShapeOptions, ToTypescript};
+
+fn is_false(value: &bool) -> bool {
+  !value
+}
+
+#[allow(unused)]
+#[derive(Shape, serde::Serialize)]
+struct Item {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  name: Option<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  tags: Vec<String>,
+  #[serde(skip_serializing_if = "String::is_empty")]
+  note: String,
+  #[serde(skip_serializing_if = "HashMap::is_empty")]
+  meta: HashMap<String, u8>,
+  #[serde(skip_serializing_if = "is_false")]
+  hidden: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  parent: Option<Option<u8>>,
+}
+
+#[test]
+fn known_predicates() {
+  eq!(
+    Item::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ name?: string; tags?: [string, ...string[]]; note?: string; meta?: { [key: string]: number }; hidden?: true; parent?: (number | null); }"
+  );
+
+  // the deserialize shape is not affected
+  eq!(
+    Item::shape(&ShapeOptions::for_deserialize()).to_typescript(),
+    "{ name?: (string | null | undefined); tags: Array<string>; note: string; meta: { [key: string]: number }; hidden: boolean; parent?: ((number | null | undefined) | null | undefined); }"
+  );
+}
+
+#[test]
+fn serialized_values_are_assignable() {
+  let ty = Item::shape(&ShapeOptions::for_serialize());
+
+  let empty = Item { name: None, tags: vec![], note: String::new(), meta: HashMap::new(), hidden: false, parent: None };
+  let value = serde_json::to_value(empty).unwrap();
+  eq!(value, json!({}));
+  assert!(ty.is_assignable(&value));
+
+  let full = Item {
+    name: Some("a".into()),
+    tags: vec!["b".into()],
+    note: "c".into(),
+    meta: HashMap::from([("d".into(), 1)]),
+    hidden: true,
+    parent: Some(None),
+  };
+  assert!(ty.is_assignable(&serde_json::to_value(full).unwrap()));
+
+  assert!(!ty.is_assignable(&json!({ "name": null })));
+  assert!(!ty.is_assignable(&json!({ "tags": [] })));
+  assert!(!ty.is_assignable(&json!({ "hidden": false })));
+}
+
+mod other {
+  use shape::Shape;
+
+  pub mod predicates {
+    pub fn is_empty(value: &[u8]) -> bool {
+      value.is_empty()
+    }
+  }
+
+  pub fn is_false(value: &u8) -> bool {
+    *value == 0
+  }
+
+  #[allow(unused)]
+  #[derive(Shape, serde::Serialize)]
+  pub struct Other {
+    // the narrowing only applies to booleans
+    #[serde(skip_serializing_if = "is_false")]
+    n: u8,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    o: Option<u8>,
+    // unknown paths are opaque predicates
+    #[serde(skip_serializing_if = "predicates::is_empty")]
+    e: Vec<u8>,
+  }
+}
+
+#[test]
+fn unknown_predicates() {
+  eq!(
+    other::Other::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ n?: number; o?: number; e?: Array<number>; }"
+  );
+}
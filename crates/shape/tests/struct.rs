@@ -11,6 +11,7 @@ fn empty_struct() {
 
   let expected = Type::Object(Object {
     properties: IndexMap::new(),
+    exact: false,
   });
 
   eq!(EmptyStruct::shape(&ShapeOptions::for_serialize()), expected);
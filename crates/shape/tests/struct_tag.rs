@@ -0,0 +1,45 @@
+mod common;
+
+use serde_json::json;
+use shape::{Shape, ShapeOptions, ToTypescript, Validate};
+
+#[allow(unused)]
+#[derive(Shape, serde::Serialize)]
+#[serde(tag = "type")]
+struct Ping {
+  id: u32,
+}
+
+#[allow(unused)]
+#[derive(Shape, serde::Serialize)]
+#[serde(tag = "kind", rename = "pong")]
+struct Pong {}
+
+#[allow(unused)]
+#[derive(Shape, serde::Serialize)]
+#[serde(tag = "kind", rename(serialize = "ack", deserialize = "Ack"))]
+struct Ack {
+  id: u32,
+}
+
+#[test]
+fn struct_tag() {
+  eq!(
+    Ping::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ type: \"Ping\"; id: number; }"
+  );
+  // serde ignores the tag of structs when deserializing
+  eq!(Ping::shape(&ShapeOptions::for_deserialize()).to_typescript(), "{ id: number; }");
+
+  eq!(Pong::shape(&ShapeOptions::for_serialize()).to_typescript(), "{ kind: \"pong\"; }");
+  eq!(Ack::shape(&ShapeOptions::for_serialize()).to_typescript(), "{ kind: \"ack\"; id: number; }");
+}
+
+#[test]
+fn serialized_value_validates() {
+  let ty = Ping::shape(&ShapeOptions::for_serialize());
+  let value = serde_json::to_value(Ping { id: 1 }).unwrap();
+  eq!(value, json!({ "type": "Ping", "id": 1 }));
+  ty.validate(&value).unwrap();
+  assert!(ty.validate(&json!({ "id": 1 })).is_err());
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use shape::{Array, Definitions, Literal, Object, Property, Record, Shape, ShapeOptions, Tuple, Type};
+
+fn lit(s: &str) -> Type {
+  Type::Literal(Literal::String(s.into()))
+}
+
+fn object(props: &[(&str, Type, bool)]) -> Type {
+  Type::Object(Object {
+    properties: props
+      .iter()
+      .map(|(key, ty, optional)| (key.to_string(), Property { ty: ty.clone(), optional: *optional, readonly: false, description: None }))
+      .collect(),
+    exact: false,
+  })
+}
+
+#[test]
+fn primitives_and_literals() {
+  assert!(Type::String.is_subtype_of(&Type::String));
+  assert!(!Type::String.is_subtype_of(&Type::Number));
+  assert!(lit("a").is_subtype_of(&Type::String));
+  assert!(!Type::String.is_subtype_of(&lit("a")));
+  assert!(Type::Literal(Literal::Number(1.0)).is_subtype_of(&Type::Number));
+  assert!(Type::Never.is_subtype_of(&Type::String));
+  assert!(!Type::String.is_subtype_of(&Type::Never));
+  assert!(Type::Number.is_subtype_of(&Type::Custom("any".into())));
+  assert!(!Type::Number.is_subtype_of(&Type::Custom("Date".into())));
+}
+
+#[test]
+fn unions_and_intersections() {
+  let a_or_b = Type::Or(vec![lit("a"), lit("b")]);
+  assert!(lit("a").is_subtype_of(&a_or_b));
+  assert!(a_or_b.is_subtype_of(&Type::String));
+  assert!(a_or_b.is_subtype_of(&Type::Or(vec![lit("b"), lit("a"), lit("c")])));
+  assert!(!Type::Or(vec![lit("a"), lit("c")]).is_subtype_of(&a_or_b));
+
+  let bool_lits = Type::Or(vec![Type::Literal(Literal::Boolean(true)), Type::Literal(Literal::Boolean(false))]);
+  assert!(Type::Boolean.is_subtype_of(&bool_lits));
+  assert!(bool_lits.is_subtype_of(&Type::Boolean));
+
+  let and = Type::And(vec![object(&[("a", Type::String, false)]), object(&[("b", Type::Number, false)])]);
+  let merged = object(&[("a", Type::String, false), ("b", Type::Number, false)]);
+  assert!(and.is_subtype_of(&merged));
+  assert!(merged.is_subtype_of(&and));
+  assert!(!Type::String.is_subtype_of(&Type::And(vec![])));
+}
+
+#[test]
+fn optional_properties() {
+  let required = object(&[("a", Type::String, false)]);
+  let optional = object(&[("a", Type::String, true)]);
+  let undefined = object(&[("a", Type::Or(vec![Type::String, Type::Undefined]), false)]);
+  let empty = object(&[]);
+  let extra = object(&[("a", Type::String, false), ("b", Type::Number, false)]);
+
+  assert!(required.is_subtype_of(&optional));
+  assert!(!optional.is_subtype_of(&required));
+  assert!(empty.is_subtype_of(&optional));
+  assert!(empty.is_subtype_of(&undefined));
+  assert!(!empty.is_subtype_of(&required));
+  assert!(extra.is_subtype_of(&required));
+  assert!(!required.is_subtype_of(&extra));
+}
+
+#[test]
+fn records() {
+  let record = |key: Type, optional: bool| {
+    Type::Record(Record { optional, readonly: false, key: Box::new(key), value: Box::new(Type::Number) })
+  };
+
+  assert!(object(&[("a", Type::Number, false)]).is_subtype_of(&record(Type::String, true)));
+  assert!(!object(&[("a", Type::String, false)]).is_subtype_of(&record(Type::String, true)));
+  assert!(!object(&[("a", Type::Number, false)]).is_subtype_of(&record(lit("b"), true)));
+
+  let ab = Type::Or(vec![lit("a"), lit("b")]);
+  assert!(!object(&[("a", Type::Number, false)]).is_subtype_of(&record(ab.clone(), false)));
+  assert!(object(&[("a", Type::Number, false), ("b", Type::Number, false)]).is_subtype_of(&record(ab.clone(), false)));
+
+  assert!(record(Type::String, true).is_subtype_of(&object(&[("a", Type::Number, true)])));
+  assert!(!record(Type::String, true).is_subtype_of(&object(&[("a", Type::Number, false)])));
+  assert!(record(ab.clone(), false).is_subtype_of(&object(&[("a", Type::Number, false)])));
+
+  assert!(record(lit("a"), true).is_subtype_of(&record(ab.clone(), true)));
+  assert!(record(Type::Number, true).is_subtype_of(&record(Type::String, true)));
+  assert!(!record(Type::String, true).is_subtype_of(&record(ab, false)));
+}
+
+#[test]
+fn tuples() {
+  let tuple = |items: Vec<Type>, rest: Option<Type>| Type::Tuple(Tuple { items, rest: rest.map(Box::new), optional: 0 });
+
+  assert!(tuple(vec![lit("a"), Type::Number], None).is_subtype_of(&tuple(vec![Type::String, Type::Number], None)));
+  assert!(!tuple(vec![Type::String], None).is_subtype_of(&tuple(vec![Type::String, Type::Number], None)));
+  assert!(tuple(vec![Type::String], None).is_subtype_of(&tuple(vec![Type::String, Type::Or(vec![Type::Number, Type::Undefined])], None)));
+  assert!(!tuple(vec![Type::String, Type::Number], None).is_subtype_of(&tuple(vec![Type::String], None)));
+
+  // rest elements
+  assert!(tuple(vec![Type::String, Type::Number], None).is_subtype_of(&tuple(vec![Type::String], Some(Type::Number))));
+  assert!(tuple(vec![Type::String], Some(Type::Number)).is_subtype_of(&tuple(vec![Type::String], Some(Type::Number))));
+  assert!(!tuple(vec![Type::String], Some(Type::Number)).is_subtype_of(&tuple(vec![Type::String], None)));
+  assert!(!tuple(vec![Type::String], Some(Type::Number)).is_subtype_of(&tuple(vec![Type::String, Type::Number], None)));
+
+  let numbers = Type::Array(Array { item: Box::new(Type::Number) });
+  assert!(tuple(vec![Type::Number, Type::Number], Some(Type::Number)).is_subtype_of(&numbers));
+  assert!(!tuple(vec![Type::Number, Type::String], None).is_subtype_of(&numbers));
+  assert!(numbers.is_subtype_of(&tuple(vec![], Some(Type::Number))));
+  assert!(!numbers.is_subtype_of(&tuple(vec![Type::Number], None)));
+}
+
+#[test]
+fn serialize_to_deserialize() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Server {
+    id: u64,
+    name: Option<String>,
+    tags: HashMap<String, u32>,
+    extra: bool,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Client {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, u32>,
+    #[serde(default)]
+    added: bool,
+  }
+
+  let server = Server::shape(&ShapeOptions::for_serialize());
+  let client = Client::shape(&ShapeOptions::for_deserialize());
+  assert!(server.is_subtype_of(&client));
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct StrictClient {
+    id: u64,
+    added: bool,
+  }
+
+  assert!(!server.is_subtype_of(&StrictClient::shape(&ShapeOptions::for_deserialize())));
+}
+
+#[test]
+fn recursive_definitions() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Tree {
+    value: u32,
+    children: Vec<Tree>,
+  }
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct LooseTree {
+    value: f64,
+    #[serde(default)]
+    children: Vec<LooseTree>,
+  }
+
+  let mut server = Definitions::new();
+  let a = server.add::<Tree>(&ShapeOptions::for_serialize());
+
+  let mut client = Definitions::new();
+  let b = client.add::<LooseTree>(&ShapeOptions::for_deserialize());
+
+  assert!(a.is_subtype_of_with(&b, &server, &client));
+  assert!(!b.is_subtype_of_with(&a, &client, &server));
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use serde_json::json;
+use shape::{json_schema, Array, Literal, Object, Property, Record, Shape, ShapeOptions, ToJsonSchema, Tuple, Type};
+
+#[test]
+fn primitives() {
+  eq!(Type::String.to_json_schema(), json!({ "type": "string" }));
+  eq!(Type::Number.to_json_schema(), json!({ "type": "number" }));
+  eq!(Type::Boolean.to_json_schema(), json!({ "type": "boolean" }));
+  eq!(Type::Null.to_json_schema(), json!({ "type": "null" }));
+  eq!(Type::Never.to_json_schema(), json!(false));
+  eq!(Type::Custom("Date".into()).to_json_schema(), json!({}));
+}
+
+#[test]
+fn literals() {
+  eq!(Type::Literal(Literal::String("a".into())).to_json_schema(), json!({ "const": "a" }));
+  eq!(Type::Literal(Literal::Number(1.5)).to_json_schema(), json!({ "const": 1.5 }));
+  eq!(Type::Literal(Literal::Boolean(true)).to_json_schema(), json!({ "const": true }));
+}
+
+#[test]
+fn array_and_tuple() {
+  eq!(
+    Type::Array(Array { item: Box::new(Type::String) }).to_json_schema(),
+    json!({ "type": "array", "items": { "type": "string" } })
+  );
+
+  eq!(
+    Type::Tuple(Tuple { items: vec![Type::String, Type::Number], rest: None, optional: 0 }).to_json_schema(),
+    json!({
+      "type": "array",
+      "prefixItems": [{ "type": "string" }, { "type": "number" }],
+      "minItems": 2,
+      "items": false,
+    })
+  );
+
+  eq!(
+    Type::Tuple(Tuple { items: vec![Type::String], rest: Some(Box::new(Type::Boolean)), optional: 0 }).to_json_schema(),
+    json!({
+      "type": "array",
+      "prefixItems": [{ "type": "string" }],
+      "minItems": 1,
+      "items": { "type": "boolean" },
+    })
+  );
+}
+
+#[test]
+fn object() {
+  let ty = Type::Object(Object {
+    properties: indexmap::IndexMap::from([
+      ("a".into(), Property { ty: Type::String, optional: false, readonly: false, description: None }),
+      ("b".into(), Property { ty: Type::Number, optional: true, readonly: true, description: None }),
+      ("c".into(), Property { ty: Type::Or(vec![Type::Boolean, Type::Undefined]), optional: false, readonly: false, description: None }),
+    ]),
+    exact: false,
+  });
+
+  eq!(
+    ty.to_json_schema(),
+    json!({
+      "type": "object",
+      "properties": {
+        "a": { "type": "string" },
+        "b": { "type": "number", "readOnly": true },
+        "c": { "anyOf": [{ "type": "boolean" }] },
+      },
+      "required": ["a"],
+    })
+  );
+}
+
+#[test]
+fn record() {
+  eq!(
+    Type::Record(Record { optional: false, readonly: false, key: Box::new(Type::String), value: Box::new(Type::Number) }).to_json_schema(),
+    json!({ "type": "object", "additionalProperties": { "type": "number" } })
+  );
+
+  let keys = Type::Or(vec![Type::Literal(Literal::String("a".into())), Type::Literal(Literal::String("b".into()))]);
+  eq!(
+    Type::Record(Record { optional: true, readonly: false, key: Box::new(keys), value: Box::new(Type::Number) }).to_json_schema(),
+    json!({
+      "type": "object",
+      "additionalProperties": { "type": "number" },
+      "propertyNames": { "anyOf": [{ "const": "a" }, { "const": "b" }] },
+    })
+  );
+}
+
+#[test]
+fn or_and() {
+  eq!(
+    Type::Or(vec![Type::String, Type::Null]).to_json_schema(),
+    json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+  );
+  eq!(
+    Type::And(vec![Type::String, Type::Number]).to_json_schema(),
+    json!({ "allOf": [{ "type": "string" }, { "type": "number" }] })
+  );
+  eq!(Type::Or(vec![]).to_json_schema(), json!(false));
+}
+
+#[test]
+fn document() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Node {
+    name: Option<String>,
+    children: Vec<Node>,
+  }
+
+  eq!(
+    json_schema::<Node>(&ShapeOptions::for_serialize()),
+    json!({
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "$ref": "#/$defs/Node",
+      "$defs": {
+        "Node": {
+          "type": "object",
+          "properties": {
+            "name": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } },
+          },
+          "required": ["name", "children"],
+        }
+      }
+    })
+  );
+
+  eq!(
+    json_schema::<Node>(&ShapeOptions::for_deserialize()),
+    json!({
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "$ref": "#/$defs/Node",
+      "$defs": {
+        "Node": {
+          "type": "object",
+          "properties": {
+            "name": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "children": { "type": "array", "items": { "$ref": "#/$defs/Node" } },
+          },
+          "required": ["children"],
+        }
+      }
+    })
+  );
+}
+
+#[test]
+fn document_without_definitions() {
+  eq!(
+    json_schema::<Vec<u8>>(&ShapeOptions::for_serialize()),
+    json!({
+      "$schema": "https://json-schema.org/draft/2020-12/schema",
+      "type": "array",
+      "items": { "type": "number" },
+    })
+  );
+}
@@ -1,4 +1,4 @@
-use shape::{Array, Literal, Object, Property, Record, Shape, ShapeOptions, Type};
+use shape::{Array, Literal, Object, Property, Record, Shape, ShapeOptions, Tuple, Type};
 use shape::ToTypescript;
 use text_diff::print_diff;
 
@@ -91,11 +91,31 @@ fn array() {
 fn tuple() {
   eq!(<(String, i32)>::shape(&ShapeOptions::for_serialize()), "[string,number]");
   eq!(<(String, i32)>::shape(&ShapeOptions::for_deserialize()), "[string,number]");
+
+  eq!(
+    Type::Tuple(Tuple { items: vec![Type::String, Type::Number, Type::Boolean], optional: 2, rest: None }),
+    "[string,number?,boolean?]"
+  );
+  eq!(
+    Type::Tuple(Tuple { items: vec![Type::String], optional: 0, rest: Some(Box::new(Type::Number)) }),
+    "[string,...number[]]"
+  );
 }
 
 #[test]
 fn record() {
   eq!(Type::Record(Record { optional: false, readonly: false, key: Box::new(Type::String), value: Box::new(Type::Number) }), "{[key:string]:number}");
+
+  #[derive(Shape, PartialEq, Eq, Hash)]
+  #[allow(unused)]
+  #[serde(rename_all = "lowercase")]
+  enum Kind {
+    A,
+    B,
+  }
+
+  eq!(std::collections::HashMap::<Kind, u8>::shape(&ShapeOptions::for_serialize()), r#"Partial<Record<("a" | "b"), number>>"#);
+  eq!(std::collections::HashMap::<u64, u8>::shape(&ShapeOptions::for_serialize()), "Record<`${number}`, number>");
 }
 
 #[test]
@@ -108,7 +128,8 @@ fn object() {
           Property { 
             ty: Type::String,
             optional: false,
-            readonly: false
+            readonly: false,
+            description: None,
           }
         ),
         (
@@ -116,10 +137,12 @@ fn object() {
           Property { 
             ty: Type::Number,
             optional: false,
-            readonly: false
+            readonly: false,
+            description: None,
           }
         )
-      ])
+      ]),
+      exact: false,
     }
   );
 
@@ -167,6 +190,7 @@ fn quoted() {
         ty: Type::String,
         optional: false,
         readonly: false,
+        description: None,
       },
     ), (
       "2two".into(),
@@ -174,6 +198,7 @@ fn quoted() {
         ty: Type::Number,
         optional: true,
         readonly: true,
+        description: None,
       },
     ), (
       "".into(),
@@ -181,8 +206,10 @@ fn quoted() {
         ty: Type::Boolean,
         optional: false,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(shape, "{\"quoted-key\":string;readonly \"2two\"?:number;\"\":boolean;}");
@@ -197,8 +224,10 @@ fn readonly() {
         ty: Type::String,
         optional: false,
         readonly: true,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(shape, "{readonly key:string;}");
@@ -213,8 +242,10 @@ fn optional() {
         ty: Type::String,
         optional: true,
         readonly: false,
+        description: None,
       },
     )]),
+    exact: false,
   });
 
   eq!(shape, "{key?:string;}");
@@ -0,0 +1,136 @@
+use shape::{Array, Literal, Object, Property, Record, Shape, ShapeOptions, ToZod, Tuple, Type, TypescriptExporter};
+use text_diff::print_diff;
+
+macro_rules! eq {
+  ($a:expr, $b:expr) => {
+    let a = $a.to_zod();
+    let b = String::from($b);
+    if a != b {
+      print_diff(&a, &b, " ");
+      panic!("$a != $b");
+    }
+  };
+}
+
+#[test]
+fn primitives() {
+  eq!(Type::String, "z.string()");
+  eq!(Type::Number, "z.number()");
+  eq!(Type::Boolean, "z.boolean()");
+  eq!(Type::Null, "z.null()");
+  eq!(Type::Undefined, "z.undefined()");
+  eq!(Type::Never, "z.never()");
+  eq!(Type::Custom("any".into()), "z.any()");
+  eq!(Type::Custom("Date".into()), "z.custom<Date>()");
+  eq!(Type::Literal(Literal::String("a".into())), "z.literal(\"a\")");
+  eq!(Type::Literal(Literal::Number(1.0)), "z.literal(1)");
+}
+
+#[test]
+fn containers() {
+  eq!(Type::Array(Array { item: Box::new(Type::String) }), "z.array(z.string())");
+  eq!(
+    Type::Tuple(Tuple { items: vec![Type::String, Type::Number], rest: Some(Box::new(Type::Boolean)), optional: 0 }),
+    "z.tuple([z.string(), z.number()]).rest(z.boolean())"
+  );
+  eq!(
+    Type::Record(Record { optional: false, readonly: false, key: Box::new(Type::String), value: Box::new(Type::Number) }),
+    "z.record(z.string(), z.number())"
+  );
+  eq!(
+    Type::Object(Object {
+      properties: indexmap::IndexMap::from([
+        ("a".into(), Property { ty: Type::String, optional: false, readonly: false, description: None }),
+        ("b-c".into(), Property { ty: Type::Number, optional: true, readonly: true, description: None }),
+      ]),
+      exact: false,
+    }),
+    "z.object({ a: z.string(), \"b-c\": z.number().optional().readonly() })"
+  );
+}
+
+#[test]
+fn options() {
+  eq!(Option::<String>::shape(&ShapeOptions::for_serialize()), "z.string().nullable()");
+  eq!(Option::<String>::shape(&ShapeOptions::for_deserialize()), "z.string().nullable().optional()");
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Struct {
+    a: Option<u32>,
+  }
+
+  eq!(Struct::shape(&ShapeOptions::for_deserialize()), "z.object({ a: z.number().nullable().optional() })");
+}
+
+#[test]
+fn unions() {
+  eq!(Type::Or(vec![Type::String, Type::Number]), "z.union([z.string(), z.number()])");
+  eq!(Type::Or(vec![]), "z.never()");
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[serde(tag = "type")]
+  enum Internal {
+    A,
+    B { value: u32 },
+  }
+
+  eq!(
+    Internal::shape(&ShapeOptions::for_serialize()),
+    "z.discriminatedUnion(\"type\", [z.object({ type: z.literal(\"A\") }), z.object({ type: z.literal(\"B\"), value: z.number() })])"
+  );
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[serde(tag = "t", content = "c")]
+  enum Adjacent {
+    A(String),
+    B(u32),
+  }
+
+  eq!(
+    Adjacent::shape(&ShapeOptions::for_serialize()),
+    "z.discriminatedUnion(\"t\", [z.object({ t: z.literal(\"A\"), c: z.string() }), z.object({ t: z.literal(\"B\"), c: z.number() })])"
+  );
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  enum External {
+    A(String),
+    B(u32),
+  }
+
+  eq!(
+    External::shape(&ShapeOptions::for_serialize()),
+    "z.union([z.object({ A: z.string() }), z.object({ B: z.number() })])"
+  );
+}
+
+#[test]
+fn intersections() {
+  eq!(Type::And(vec![Type::String, Type::Number, Type::Boolean]), "z.intersection(z.intersection(z.string(), z.number()), z.boolean())");
+}
+
+#[test]
+fn export() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Tree {
+    children: Vec<Tree>,
+  }
+
+  let mut exporter = TypescriptExporter::new(ShapeOptions::for_serialize());
+  exporter.add::<Tree>();
+
+  let a = exporter.export_zod();
+  let b = "import { z } from \"zod\";
+
+export type Tree = { children: Array<Tree>; };
+export const Tree: z.ZodType<Tree> = z.object({ children: z.array(z.lazy(() => Tree)) });
+";
+  if a != b {
+    print_diff(&a, b, "\n");
+    panic!("export != expected");
+  }
+}
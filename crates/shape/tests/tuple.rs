@@ -1,4 +1,5 @@
-use shape::{Shape, ShapeOptions, Tuple, Type};
+use serde_json::json;
+use shape::{IsAsignable, Shape, ShapeOptions, ToJsonSchema, ToTypescript, ToZod, Tuple, Type, Validate};
 
 mod common;
 
@@ -7,8 +8,23 @@ fn tuple() {
   let expected = Type::Tuple(Tuple {
     items: vec![Type::Number, Type::String],
     rest: None,
+    optional: 0,
   });
 
   eq!(<(i32, String)>::shape(&ShapeOptions::for_serialize()), expected);
   eq!(<(i32, String)>::shape(&ShapeOptions::for_deserialize()), expected);
-}
\ No newline at end of file
+}
+
+#[test]
+fn more_optional_than_items() {
+  let tuple = Tuple { items: vec![Type::Number], rest: None, optional: 2 };
+  eq!(tuple.required(), 0);
+
+  let ty = Type::Tuple(tuple);
+  eq!(ty.to_typescript(), "[number?]");
+  eq!(ty.to_zod(), "z.tuple([z.number().optional()])");
+  eq!(ty.to_json_schema()["minItems"], json!(0));
+  assert!(ty.is_assignable(&json!([])));
+  assert!(ty.validate(&json!([])).is_ok());
+  assert!(ty.is_subtype_of(&ty));
+}
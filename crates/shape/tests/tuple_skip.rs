@@ -1,6 +1,6 @@
 use serde::Serialize;
 use serde_json::json;
-use shape::{Shape, ShapeOptions, Tuple, Type};
+use shape::{Shape, ShapeOptions, ToTypescript, Tuple, Type};
 
 mod common;
 
@@ -39,6 +39,7 @@ fn tuple_skip_if() {
             Type::String,
             Type::Boolean,
           ],
+          optional: 0,
           rest: None,
         }
       ),
@@ -49,6 +50,7 @@ fn tuple_skip_if() {
             Type::Number,
             Type::Boolean,
           ],
+          optional: 0,
           rest: None,
         }
       )
@@ -68,6 +70,7 @@ fn tuple_skip_if_two() {
       Type::Tuple(
         Tuple {
           items: vec![ Type::String ],
+          optional: 0,
           rest: None,
         }
       ),
@@ -77,6 +80,7 @@ fn tuple_skip_if_two() {
             Type::String,
             Type::Number,
           ],
+          optional: 0,
           rest: None,
         }
       ),
@@ -86,6 +90,7 @@ fn tuple_skip_if_two() {
             Type::String,
             Type::Boolean,
           ],
+          optional: 0,
           rest: None,
         }
       ),
@@ -96,6 +101,7 @@ fn tuple_skip_if_two() {
             Type::Number,
             Type::Boolean,
           ],
+          optional: 0,
           rest: None,
         }
       )
@@ -103,6 +109,43 @@ fn tuple_skip_if_two() {
   );
 }
 
+#[test]
+fn tuple_skip_if_last() {
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Ty(String, #[serde(skip_serializing_if="is_default")] u8);
+
+  // only the last element can be missing
+  eq!(
+    Ty::shape(&ShapeOptions::for_serialize()),
+    Type::Tuple(
+      Tuple {
+        items: vec![
+          Type::String,
+          Type::Number,
+        ],
+        rest: None,
+        optional: 1,
+      }
+    )
+  );
+}
+
+#[test]
+fn tuple_skip_if_first() {
+  use shape::Validate;
+
+  #[derive(Shape)]
+  #[allow(unused)]
+  struct Ty(#[serde(skip_serializing_if="Option::is_none")] Option<u8>, String, bool);
+
+  let ty = Ty::shape(&ShapeOptions::for_serialize());
+  assert!(ty.validate(&json!(["s", true])).is_ok());
+  assert!(ty.validate(&json!([1, "s", true])).is_ok());
+  assert!(ty.validate(&json!([1, "s"])).is_err());
+  assert!(ty.validate(&json!(["s"])).is_err());
+}
+
 #[test]
 fn tuple_skip_deserializing() {
   #[derive(Serialize, Shape)]
@@ -117,6 +160,7 @@ fn tuple_skip_deserializing() {
           Type::Boolean,
         ],
         rest: None,
+        optional: 0,
       }
     )
   );
@@ -129,27 +173,17 @@ fn tuple_default() {
 
   eq!(
     Ty::shape(&ShapeOptions::for_deserialize()),
-    Type::Or(vec![
-      Type::Tuple(
-        Tuple {
-          items: vec![
-            Type::String,
-            Type::Number,
-          ],
-          rest: None,
-        }
-      ),
-      Type::Tuple(
-        Tuple {
-          items: vec![
-            Type::String,
-            Type::Number,
-            Type::Boolean,
-          ],
-          rest: None,
-        }
-      )
-    ])
+    Type::Tuple(
+      Tuple {
+        items: vec![
+          Type::String,
+          Type::Number,
+          Type::Boolean,
+        ],
+        rest: None,
+        optional: 1,
+      }
+    )
   );
 }
 
@@ -161,32 +195,40 @@ fn tuple_default_two() {
 
   eq!(
     Ty::shape(&ShapeOptions::for_deserialize()),
-    Type::Or(vec![
-      Type::Tuple(
-        Tuple {
-          items: vec![ Type::String ],
-          rest: None,
-        }
-      ),
-      Type::Tuple(
-        Tuple {
-          items: vec![
-            Type::String,
-            Type::Number,
-          ],
-          rest: None,
-        }
-      ),
-      Type::Tuple(
-        Tuple {
-          items: vec![
-            Type::String,
-            Type::Number,
-            Type::Boolean,
-          ],
-          rest: None,
-        }
-      )
-    ])
+    Type::Tuple(
+      Tuple {
+        items: vec![
+          Type::String,
+          Type::Number,
+          Type::Boolean,
+        ],
+        rest: None,
+        optional: 2,
+      }
+    )
+  );
+}
+#[test]
+fn tuple_skip_if_many() {
+  #[derive(Serialize, Shape)]
+  #[allow(unused)]
+  struct Ty(
+    String,
+    #[serde(skip_serializing_if="is_default")] u8,
+    #[serde(skip_serializing_if="is_default")] bool,
+    #[serde(skip_serializing_if="is_default")] u8,
+    #[serde(skip_serializing_if="is_default")] bool,
+    #[serde(skip_serializing_if="is_default")] u8,
+    #[serde(skip_serializing_if="is_default")] bool,
+    #[serde(skip_serializing_if="is_default")] u8,
+    #[serde(skip_serializing_if="is_default")] bool,
+    #[serde(skip_serializing_if="is_default")] String,
+  );
+
+  // too many tuples, each item is any of the fields that can be shifted to it
+  eq!(
+    Ty::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "[string, (number | boolean | string)?, (boolean | number | string)?, (number | boolean | string)?, (boolean | number | string)?, \
+     (number | boolean | string)?, (boolean | number | string)?, (number | boolean | string)?, (boolean | string)?, string?]"
   );
-}
\ No newline at end of file
+}
@@ -11,6 +11,7 @@ fn tuple_primitive() {
   let expected = Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: None,
+    optional: 0,
   });
 
   eq!(TupleStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -26,6 +27,7 @@ fn tuple_skip() {
   let expected = Type::Tuple(Tuple {
     items: vec![Type::String],
     rest: None,
+    optional: 0,
   });
 
   eq!(TupleStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -41,11 +43,13 @@ fn tuple_skip_serializing() {
   let ser = Type::Tuple(Tuple {
     items: vec![Type::String],
     rest: None,
+    optional: 0,
   });
 
   let de = Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: None,
+    optional: 0,
   });
 
   eq!(TupleStruct::shape(&ShapeOptions::for_serialize()), ser);
@@ -61,11 +65,13 @@ fn tuple_skip_deserializing() {
   let de = Type::Tuple(Tuple {
     items: vec![Type::String],
     rest: None,
+    optional: 0,
   });
 
   let ser = Type::Tuple(Tuple {
     items: vec![Type::String, Type::Number],
     rest: None,
+    optional: 0,
   });
 
   eq!(TupleStruct::shape(&ShapeOptions::for_serialize()), ser);
@@ -81,6 +87,7 @@ fn tuple_empty_skip_one() {
   let expected = Type::Tuple(Tuple {
     items: vec![],
     rest: None,
+    optional: 0,
   });
 
   eq!(TupleStruct::shape(&ShapeOptions::for_serialize()), expected);
@@ -0,0 +1,99 @@
+mod common;
+
+use shape::{Shape, ShapeOptions, ToTypescript, Type, Validate};
+
+mod timestamp {
+  use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+  use serde::{Deserialize, Serialize};
+
+  /// seconds since the epoch
+  pub fn serialize<S: serde::Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok, S::Error> {
+    let seconds = time.duration_since(UNIX_EPOCH).map_err(serde::ser::Error::custom)?.as_secs();
+    (seconds as i64).serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<SystemTime, D::Error> {
+    let seconds = i64::deserialize(deserializer)?;
+    let seconds = u64::try_from(seconds).map_err(serde::de::Error::custom)?;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+  }
+}
+
+#[test]
+fn field_overrides() {
+  #[allow(unused)]
+  #[derive(Shape, serde::Serialize, serde::Deserialize)]
+  struct Event {
+    #[shape(type = "Date")]
+    at: String,
+    #[serde(with = "timestamp")]
+    #[shape(as = i64)]
+    created: std::time::SystemTime,
+    #[shape(as = "Option<Vec<u8>>")]
+    data: Option<String>,
+  }
+
+  let ser = Event::shape(&ShapeOptions::for_serialize());
+  eq!(ser.to_typescript(), "{ at: Date; created: number; data: (Array<number> | null); }");
+
+  let de = Event::shape(&ShapeOptions::for_deserialize());
+  eq!(de.to_typescript(), "{ at: Date; created: number; data?: (Array<number> | null | undefined); }");
+
+  let event = Event { at: String::new(), created: std::time::UNIX_EPOCH + std::time::Duration::from_secs(60), data: None };
+  let value = serde_json::to_value(&event).unwrap();
+  eq!(value["created"], serde_json::json!(60));
+  assert!(ser.validate(&value).is_ok());
+}
+
+#[test]
+fn generic_overrides() {
+  use std::collections::{BTreeMap, HashMap};
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Counts {
+    #[shape(as = HashMap<String, i64>, rename = "n")]
+    counts: Vec<(String, i64)>,
+    #[shape(rename = "b", as = BTreeMap<u8, Vec<String>>)]
+    by_id: Vec<String>,
+  }
+
+  eq!(
+    Counts::shape(&ShapeOptions::for_serialize()).to_typescript(),
+    "{ n: { [key: string]: number }; b: Record<`${number}`, Array<string>>; }"
+  );
+}
+
+#[test]
+fn tuple_overrides() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Pair(#[shape(type = "bigint")] u128, #[shape(as = String)] u32);
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Id(#[shape(as = String)] u64);
+
+  eq!(Pair::shape(&ShapeOptions::for_serialize()).to_typescript(), "[bigint, string]");
+  eq!(Id::shape(&ShapeOptions::for_serialize()), Type::String);
+}
+
+#[test]
+fn container_overrides() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[shape(type = "Date")]
+  struct Timestamp(u64);
+
+  #[allow(unused)]
+  #[derive(Shape)]
+  #[shape(as = String)]
+  struct Email {
+    user: String,
+    domain: String,
+  }
+
+  eq!(Timestamp::shape(&ShapeOptions::for_serialize()), Type::Custom("Date".into()));
+  eq!(Email::shape(&ShapeOptions::for_deserialize()), Type::String);
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use serde_json::json;
+use shape::{Definitions, IsAsignable, Literal, Shape, ShapeOptions, Tuple, Type, Validate, ValidationError};
+
+#[allow(unused)]
+#[derive(Shape)]
+struct User {
+  name: String,
+  tags: Vec<String>,
+}
+
+#[allow(unused)]
+#[derive(Shape)]
+#[serde(tag = "type")]
+enum Event {
+  Created { user: User },
+  Deleted { id: u64 },
+}
+
+#[test]
+fn valid() {
+  let ty = User::shape(&ShapeOptions::for_deserialize());
+  eq!(ty.validate(&json!({ "name": "a", "tags": ["b"] })), Ok(()));
+}
+
+#[test]
+fn paths() {
+  let ty = User::shape(&ShapeOptions::for_deserialize());
+  let errors = ty.validate(&json!({ "tags": ["a", 1, "c", null] })).unwrap_err();
+
+  eq!(
+    errors,
+    vec![
+      ValidationError { path: "/name".into(), expected: Type::String, found: "missing".into(), closest: None },
+      ValidationError { path: "/tags/1".into(), expected: Type::String, found: "number 1".into(), closest: None },
+      ValidationError { path: "/tags/3".into(), expected: Type::String, found: "null".into(), closest: None },
+    ]
+  );
+
+  eq!(errors[1].to_string(), "/tags/1: expected string, found number 1");
+}
+
+#[test]
+fn pointer_escape() {
+  let ty = Type::Object(shape::Object {
+    properties: indexmap::IndexMap::from([(
+      "a/b~c".into(),
+      shape::Property { ty: Type::Number, optional: false, readonly: false, description: None },
+    )]),
+    exact: false,
+  });
+
+  let errors = ty.validate(&json!({ "a/b~c": "x" })).unwrap_err();
+  eq!(errors[0].path, "/a~1b~0c");
+}
+
+#[test]
+fn closest_variant() {
+  let ty = Event::shape(&ShapeOptions::for_deserialize());
+  let errors = ty.validate(&json!({ "type": "Deleted", "id": "1" })).unwrap_err();
+
+  eq!(errors.len(), 1);
+  eq!(errors[0].path, "");
+  eq!(errors[0].found, "object");
+
+  let (index, variant_errors) = errors[0].closest.clone().unwrap();
+  eq!(index, 1);
+  eq!(
+    variant_errors,
+    vec![ValidationError { path: "/id".into(), expected: Type::Number, found: "string \"1\"".into(), closest: None }]
+  );
+
+  let errors = ty.validate(&json!({ "type": "Created", "user": { "name": 1, "tags": [] } })).unwrap_err();
+  let (index, variant_errors) = errors[0].closest.clone().unwrap();
+  eq!(index, 0);
+  eq!(variant_errors[0].path, "/user/name");
+}
+
+#[test]
+fn tuple_short_array() {
+  let ty = Type::Tuple(Tuple { items: vec![Type::String, Type::Number], rest: None, optional: 0 });
+
+  assert!(!ty.is_assignable(&json!(["a"])));
+  assert!(!ty.is_assignable(&json!(["a", 1, 2])));
+  assert!(ty.is_assignable(&json!(["a", 1])));
+
+  let errors = ty.validate(&json!(["a"])).unwrap_err();
+  eq!(errors, vec![ValidationError { path: "/1".into(), expected: Type::Number, found: "missing".into(), closest: None }]);
+
+  let errors = ty.validate(&json!(["a", 1, 2])).unwrap_err();
+  eq!(errors, vec![ValidationError { path: "/2".into(), expected: Type::Never, found: "number 2".into(), closest: None }]);
+
+  let optional = Type::Tuple(Tuple { items: vec![Type::String, Type::Or(vec![Type::Number, Type::Undefined])], rest: None, optional: 0 });
+  assert!(optional.is_assignable(&json!(["a"])));
+  eq!(optional.validate(&json!(["a"])), Ok(()));
+}
+
+#[test]
+fn records() {
+  let ty = Type::Record(shape::Record {
+    optional: false,
+    readonly: false,
+    key: Box::new(Type::Or(vec![
+      Type::Literal(Literal::String("a".into())),
+      Type::Literal(Literal::String("b".into())),
+    ])),
+    value: Box::new(Type::Number),
+  });
+
+  eq!(ty.validate(&json!({ "a": 1, "b": 2 })), Ok(()));
+
+  let errors = ty.validate(&json!({ "a": "1", "c": 2 })).unwrap_err();
+  eq!(errors.iter().map(|e| e.path.as_str()).collect::<Vec<_>>(), vec!["/a", "/c", "/b"]);
+  eq!(errors[1].found, "key \"c\"");
+
+  let numbers = std::collections::HashMap::<u32, bool>::shape(&ShapeOptions::for_deserialize());
+  eq!(numbers.validate(&json!({ "1": true, "2": false })), Ok(()));
+  eq!(numbers.validate(&json!({ "x": true })).unwrap_err()[0].path, "/x");
+}
+
+#[test]
+fn references() {
+  #[allow(unused)]
+  #[derive(Shape)]
+  struct Node {
+    value: u32,
+    children: Vec<Node>,
+  }
+
+  let mut definitions = Definitions::new();
+  let ty = definitions.add::<Node>(&ShapeOptions::for_deserialize());
+
+  let value = json!({ "value": 1, "children": [{ "value": 2, "children": [{ "value": "3", "children": [] }] }] });
+  let errors = ty.validate_with(&value, &definitions).unwrap_err();
+  eq!(errors.len(), 1);
+  eq!(errors[0].path, "/children/0/children/0/value");
+
+  // without definitions the reference can't be checked
+  let errors = ty.validate(&value).unwrap_err();
+  eq!(errors[0].found, "object (unresolved reference)");
+}